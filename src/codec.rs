@@ -0,0 +1,265 @@
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+
+/// Generates the `NodynEncode` trait, the `NodynDecodeError` and the built-in
+/// implementations for primitives, `String`, `Vec<T>`, `Option<T>` and `Box<T>`.
+///
+/// Integers are varints (LEB128, signed integers zigzag encoded), floats are
+/// little endian, and lengths are varint prefixes. A `Vec` of zero sized items
+/// can't be longer than its input, so a crafted length can't make decoding loop.
+#[allow(clippy::too_many_lines)]
+pub(crate) fn codec_tokens() -> TokenStream {
+    let unsigned =
+        ["u16", "u32", "u64", "u128", "usize"].map(|ty| Ident::new(ty, Span::call_site()));
+    let signed = ["i16", "i32", "i64", "i128", "isize"].map(|ty| Ident::new(ty, Span::call_site()));
+    let floats = ["f32", "f64"].map(|ty| Ident::new(ty, Span::call_site()));
+
+    quote! {
+        /// Binary encoding of the values of enums with `impl codec`.
+        pub trait NodynEncode: ::core::marker::Sized {
+            /// Appends the encoded value to `out`.
+            fn encode(&self, out: &mut ::std::vec::Vec<u8>);
+
+            /// Decodes a value from the start of `input`, returning it with the number
+            /// of bytes read.
+            ///
+            /// # Errors
+            ///
+            /// Returns an error if `input` ends early or doesn't hold a valid value.
+            fn decode(input: &[u8]) -> ::core::result::Result<(Self, usize), NodynDecodeError>;
+        }
+
+        /// Error decoding a value with [`NodynEncode`].
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum NodynDecodeError {
+            /// The input ended before the value.
+            UnexpectedEnd,
+            /// The tag doesn't belong to a variant.
+            UnknownTag(u32),
+            /// The input doesn't hold a valid value.
+            InvalidData,
+        }
+
+        impl ::core::fmt::Display for NodynDecodeError {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                match self {
+                    Self::UnexpectedEnd => f.write_str("unexpected end of input"),
+                    Self::UnknownTag(tag) => ::core::write!(f, "unknown tag {tag}"),
+                    Self::InvalidData => f.write_str("invalid data"),
+                }
+            }
+        }
+
+        impl ::core::error::Error for NodynDecodeError {}
+
+        #[doc(hidden)]
+        fn __nodyn_varint_encode(mut value: u128, out: &mut ::std::vec::Vec<u8>) {
+            while value >= 0x80 {
+                out.push((value & 0x7f) as u8 | 0x80);
+                value >>= 7;
+            }
+            out.push(value as u8);
+        }
+
+        #[doc(hidden)]
+        fn __nodyn_varint_decode(
+            input: &[u8],
+        ) -> ::core::result::Result<(u128, usize), NodynDecodeError> {
+            let mut value = 0u128;
+            for (index, &byte) in input.iter().enumerate() {
+                // the 19th byte holds the last 2 bits of a `u128`
+                if index == 18 && byte > 0x03 {
+                    return ::core::result::Result::Err(NodynDecodeError::InvalidData);
+                }
+                value |= u128::from(byte & 0x7f) << (7 * index as u32);
+                if byte & 0x80 == 0 {
+                    return ::core::result::Result::Ok((value, index + 1));
+                }
+            }
+            ::core::result::Result::Err(NodynDecodeError::UnexpectedEnd)
+        }
+
+        #[doc(hidden)]
+        fn __nodyn_take(
+            input: &[u8],
+            len: usize,
+        ) -> ::core::result::Result<&[u8], NodynDecodeError> {
+            input.get(..len).ok_or(NodynDecodeError::UnexpectedEnd)
+        }
+
+        impl NodynEncode for () {
+            fn encode(&self, _out: &mut ::std::vec::Vec<u8>) {}
+
+            fn decode(_input: &[u8]) -> ::core::result::Result<(Self, usize), NodynDecodeError> {
+                ::core::result::Result::Ok(((), 0))
+            }
+        }
+
+        impl NodynEncode for bool {
+            fn encode(&self, out: &mut ::std::vec::Vec<u8>) {
+                out.push(u8::from(*self));
+            }
+
+            fn decode(input: &[u8]) -> ::core::result::Result<(Self, usize), NodynDecodeError> {
+                match __nodyn_take(input, 1)? {
+                    [0] => ::core::result::Result::Ok((false, 1)),
+                    [1] => ::core::result::Result::Ok((true, 1)),
+                    _ => ::core::result::Result::Err(NodynDecodeError::InvalidData),
+                }
+            }
+        }
+
+        impl NodynEncode for u8 {
+            fn encode(&self, out: &mut ::std::vec::Vec<u8>) {
+                out.push(*self);
+            }
+
+            fn decode(input: &[u8]) -> ::core::result::Result<(Self, usize), NodynDecodeError> {
+                ::core::result::Result::Ok((__nodyn_take(input, 1)?[0], 1))
+            }
+        }
+
+        impl NodynEncode for i8 {
+            fn encode(&self, out: &mut ::std::vec::Vec<u8>) {
+                out.extend_from_slice(&self.to_le_bytes());
+            }
+
+            fn decode(input: &[u8]) -> ::core::result::Result<(Self, usize), NodynDecodeError> {
+                ::core::result::Result::Ok((i8::from_le_bytes([__nodyn_take(input, 1)?[0]]), 1))
+            }
+        }
+
+        #(
+            impl NodynEncode for #unsigned {
+                fn encode(&self, out: &mut ::std::vec::Vec<u8>) {
+                    __nodyn_varint_encode(*self as u128, out);
+                }
+
+                fn decode(input: &[u8]) -> ::core::result::Result<(Self, usize), NodynDecodeError> {
+                    let (value, len) = __nodyn_varint_decode(input)?;
+                    let value = <#unsigned>::try_from(value).map_err(|_| NodynDecodeError::InvalidData)?;
+                    ::core::result::Result::Ok((value, len))
+                }
+            }
+        )*
+
+        #(
+            impl NodynEncode for #signed {
+                fn encode(&self, out: &mut ::std::vec::Vec<u8>) {
+                    let value = *self as i128;
+                    __nodyn_varint_encode(((value << 1) ^ (value >> 127)) as u128, out);
+                }
+
+                fn decode(input: &[u8]) -> ::core::result::Result<(Self, usize), NodynDecodeError> {
+                    let (value, len) = __nodyn_varint_decode(input)?;
+                    let value = ((value >> 1) as i128) ^ -((value & 1) as i128);
+                    let value = <#signed>::try_from(value).map_err(|_| NodynDecodeError::InvalidData)?;
+                    ::core::result::Result::Ok((value, len))
+                }
+            }
+        )*
+
+        #(
+            impl NodynEncode for #floats {
+                fn encode(&self, out: &mut ::std::vec::Vec<u8>) {
+                    out.extend_from_slice(&self.to_le_bytes());
+                }
+
+                fn decode(input: &[u8]) -> ::core::result::Result<(Self, usize), NodynDecodeError> {
+                    const LEN: usize = ::core::mem::size_of::<#floats>();
+                    let bytes = __nodyn_take(input, LEN)?;
+                    let mut array = [0u8; LEN];
+                    array.copy_from_slice(bytes);
+                    ::core::result::Result::Ok((<#floats>::from_le_bytes(array), LEN))
+                }
+            }
+        )*
+
+        impl NodynEncode for char {
+            fn encode(&self, out: &mut ::std::vec::Vec<u8>) {
+                __nodyn_varint_encode(u128::from(u32::from(*self)), out);
+            }
+
+            fn decode(input: &[u8]) -> ::core::result::Result<(Self, usize), NodynDecodeError> {
+                let (value, len) = <u32 as NodynEncode>::decode(input)?;
+                let value = char::from_u32(value).ok_or(NodynDecodeError::InvalidData)?;
+                ::core::result::Result::Ok((value, len))
+            }
+        }
+
+        impl NodynEncode for ::std::string::String {
+            fn encode(&self, out: &mut ::std::vec::Vec<u8>) {
+                NodynEncode::encode(&self.len(), out);
+                out.extend_from_slice(self.as_bytes());
+            }
+
+            fn decode(input: &[u8]) -> ::core::result::Result<(Self, usize), NodynDecodeError> {
+                let (len, start) = <usize as NodynEncode>::decode(input)?;
+                let end = start.checked_add(len).ok_or(NodynDecodeError::InvalidData)?;
+                let bytes = __nodyn_take(input, end)?.get(start..).unwrap_or_default();
+                let value = ::std::string::String::from_utf8(bytes.to_vec())
+                    .map_err(|_| NodynDecodeError::InvalidData)?;
+                ::core::result::Result::Ok((value, end))
+            }
+        }
+
+        impl<T: NodynEncode> NodynEncode for ::std::vec::Vec<T> {
+            fn encode(&self, out: &mut ::std::vec::Vec<u8>) {
+                NodynEncode::encode(&self.len(), out);
+                for item in self {
+                    item.encode(out);
+                }
+            }
+
+            fn decode(input: &[u8]) -> ::core::result::Result<(Self, usize), NodynDecodeError> {
+                let (len, mut read) = <usize as NodynEncode>::decode(input)?;
+                // zero sized items take no bytes, so their count is limited by the input size
+                // to bound the work done for a crafted length
+                if ::core::mem::size_of::<T>() == 0 && len > input.len() {
+                    return ::core::result::Result::Err(NodynDecodeError::InvalidData);
+                }
+                // every other item takes at least a byte
+                let mut items = ::std::vec::Vec::with_capacity(len.min(input.len()));
+                for _ in 0..len {
+                    let (item, item_len) = T::decode(input.get(read..).unwrap_or_default())?;
+                    items.push(item);
+                    read += item_len;
+                }
+                ::core::result::Result::Ok((items, read))
+            }
+        }
+
+        impl<T: NodynEncode> NodynEncode for ::core::option::Option<T> {
+            fn encode(&self, out: &mut ::std::vec::Vec<u8>) {
+                match self {
+                    ::core::option::Option::Some(value) => {
+                        out.push(1);
+                        value.encode(out);
+                    }
+                    ::core::option::Option::None => out.push(0),
+                }
+            }
+
+            fn decode(input: &[u8]) -> ::core::result::Result<(Self, usize), NodynDecodeError> {
+                match <bool as NodynEncode>::decode(input)? {
+                    (true, read) => {
+                        let (value, len) = T::decode(input.get(read..).unwrap_or_default())?;
+                        ::core::result::Result::Ok((::core::option::Option::Some(value), read + len))
+                    }
+                    (false, read) => ::core::result::Result::Ok((::core::option::Option::None, read)),
+                }
+            }
+        }
+
+        impl<T: NodynEncode> NodynEncode for ::std::boxed::Box<T> {
+            fn encode(&self, out: &mut ::std::vec::Vec<u8>) {
+                T::encode(self, out);
+            }
+
+            fn decode(input: &[u8]) -> ::core::result::Result<(Self, usize), NodynDecodeError> {
+                let (value, len) = T::decode(input)?;
+                ::core::result::Result::Ok((::std::boxed::Box::new(value), len))
+            }
+        }
+    }
+}
@@ -163,23 +163,71 @@
 //! ];
 //! ```
 //!
+//! ### Naming of Generated Identifiers
+//!
+//! Generated methods and macros use the variant and enum names in `snake_case`,
+//! keeping acronyms together: `HTTPResponse` gives `is_http_response`. This can be
+//! configured with `#[nodyn(..)]` on the enum:
+//!
+//! - `is = "is_{}"`: the `is_*` methods
+//! - `accessor = "try_as_{}"`: the `try_as_*` methods, with `_ref` and `_mut` added
+//! - `vec = "{}Vec"`: the name of the standard polymorphic `Vec`
+//! - `first = "first_{}"`, and likewise `last`, `iter`, `enumerate`, `count`, `all`,
+//!   `any` and `contains`: the methods of polymorphic `Vec`s, with `_mut` added
+//! - `unwrap = "unwrap_{}"`, and likewise `expect`, `map`, `and_then` and `replace`,
+//!   and `into_or = "into_{}_or"`: the `combinators` methods
+//! - `case = "snake_acronym"`: use `"snake"` to start a word at every uppercase
+//!   letter, like `h_t_t_p_response`
+//!
+//! A variant's own `snake_case` name can be set with `#[nodyn(snake = "..")]`.
+//! Variants with the same `snake_case` name, like `HTTPResponse` and
+//! `HttpResponse`, are a compile error.
+//!
+//! ```rust
+//! #[derive(Debug, PartialEq)]
+//! struct JSONBody(String);
+//!
+//! nodyn::nodyn! {
+//!     #[derive(Debug, PartialEq)]
+//!     #[nodyn(is = "holds_{}", accessor = "as_{}")]
+//!     enum Message {
+//!         i32,
+//!         #[nodyn(snake = "body")]
+//!         JSONBody,
+//!     }
+//!     impl is_as;
+//! }
+//!
+//! let message = Message::from(JSONBody("{}".to_string()));
+//! assert!(message.holds_body());
+//! assert_eq!(message.as_body_ref(), Some(&JSONBody("{}".to_string())));
+//! assert_eq!(Message::from(1).as_i32(), Some(1));
+//! ```
+//!
 //! # Syntax
 //!
 //! ```ignore
 //! nodyn::nodyn! {
 //!     [ #[attribute] ]
 //!     [ #[module_path = "full::module::path"]]
+//!     [ #[nodyn(is = "..", accessor = "..", vec = "..", case = "..", first = "..", unwrap = "..", ..)] ]
 //!     [pub] enum EnumName [<'lifetime>] {
+//!         [ #[tag = n] ]
+//!         [ #[meta(key = "value", ..)] ]
+//!         [ #[nodyn(snake = "..")] ]
 //!         [VariantName(Type),]
 //!         [Type,]
 //!     }
 //!
-//!     [impl TryInto | is_as | introspection]
+//!     [impl TryInto | is_as | introspection | compare | FromStr | transparent(Trait, ..) | Error[(source)] | as_dyn[(Trait, ..)] | for_each_type | match_type | dispatch | combinators | serde[(tagged | untagged | adjacent)] | codec | oneof | ffi]
 //!
 //!     [impl TraitName {
 //!         fn method_name(&self, args) -> ReturnType;
 //!     }]
 //!
+//!     [impl Iterator<Item = Type> [+ DoubleEndedIterator | ExactSizeIterator | FusedIterator];]
+//!     [impl Future<Output = Type>;]
+//!
 //!     [impl {
 //!         fn method_name(&self, args) -> ReturnType;
 //!     }]
@@ -233,6 +281,197 @@
 //! assert_eq!(i64::try_from(foo), Ok(42i64));
 //! ```
 //!
+//! ### Tags with `#[tag = n]`
+//!
+//! The position of a variant or its `type_name()` are not stable identities:
+//! they change when variants are reordered or types move to another module.
+//! Give every variant a `u32` tag with `#[tag = n]` to generate `tag()`, `kind()`
+//! and `TAGS` on the enum, and an `EnumNameKind` enum without values, with the
+//! tags as discriminant and `from_tag`. Tags must be unique and set on all
+//! variants when one has a tag.
+//!
+//! ```rust
+//! nodyn::nodyn! {
+//!     enum Value {
+//!         #[tag = 10]
+//!         i32,
+//!         #[tag = 2]
+//!         String,
+//!     }
+//! }
+//!
+//! let value = Value::from("text".to_string());
+//! assert_eq!(value.tag(), 2);
+//! assert_eq!(value.kind(), ValueKind::String);
+//! assert_eq!(Value::TAGS, [10, 2]);
+//! assert_eq!(ValueKind::from_tag(10), Some(ValueKind::I32));
+//! ```
+//!
+//! ### Metadata with `#[meta]`
+//!
+//! Constant strings like labels, icons or units can be attached to variants with
+//! `#[meta(key = "value", ..)]`. Each key generates a `const fn key(&self) -> &'static str`
+//! on the enum and on `EnumNameKind`, and the enum gets a `META` table with the
+//! values of each kind in the order of `META_KEYS`. All variants need the keys
+//! used by one of them. The names of generated items, `kind`, `tag`, `from_tag`,
+//! `TAGS`, `META` and `META_KEYS`, can't be keys.
+//!
+//! ```rust
+//! nodyn::nodyn! {
+//!     enum Reading {
+//!         #[meta(label = "Count", unit = "")]
+//!         u32,
+//!         #[meta(label = "Distance", unit = "m")]
+//!         f64,
+//!     }
+//! }
+//!
+//! let reading = Reading::from(2.5);
+//! assert_eq!(reading.label(), "Distance");
+//! assert_eq!(ReadingKind::U32.label(), "Count");
+//! assert_eq!(Reading::META_KEYS, ["label", "unit"]);
+//! assert_eq!(Reading::META[1], (ReadingKind::F64, ["Distance", "m"]));
+//! ```
+//!
+//! ### Serde (with `serde`)
+//!
+//! The `serde` feature implements `Serialize` and `Deserialize` for the enum using
+//! your `serde` dependency, which needs its `derive` feature. Variants are named by
+//! `#[serde(rename = "..")]`, their `#[tag = n]` or their type name. Other
+//! `serde` attributes on variants are rejected. The representation is chosen
+//! with an argument:
+//!
+//! - `impl serde(tagged)` or `impl serde`: `{"name": value}`
+//! - `impl serde(untagged)`: only the value, deserialized as the first variant that
+//!   succeeds
+//! - `impl serde(adjacent)`: `{"tag": "name", "content": value}`
+//!
+//! Polymorphic `Vec`s serialize as plain sequences, and implement `Deserialize`
+//! when they implement `Default`.
+//!
+//! ```rust
+//! nodyn::nodyn! {
+//!     #[derive(Debug, PartialEq)]
+//!     enum Value {
+//!         i32,
+//!         #[serde(rename = "text")]
+//!         String,
+//!     }
+//!     vec;
+//!     impl serde;
+//! }
+//!
+//! let json = serde_json::to_string(&value_vec![1, "a".to_string()]).unwrap();
+//! assert_eq!(json, r#"[{"i32":1},{"text":"a"}]"#);
+//! let value: Value = serde_json::from_str(r#"{"text":"b"}"#).unwrap();
+//! assert_eq!(value, Value::String("b".to_string()));
+//! ```
+//!
+//! ### Binary Codec (with `codec`)
+//!
+//! For targets without `serde`, the `codec` feature implements a compact binary
+//! encoding with `encode(&self, out: &mut Vec<u8>)` and
+//! `decode(&[u8]) -> Result<(Self, usize), NodynDecodeError>`, which also returns
+//! the number of bytes read. A value is encoded as its `#[tag = n]`, or its variant
+//! position, as a varint followed by the wrapped value.
+//!
+//! The wrapped values are encoded with the `NodynEncode` trait, which is defined
+//! in your crate by invoking `nodyn::codec!()` once, together with implementations
+//! for the primitives, `String`, `Vec<T>`, `Option<T>` and `Box<T>`. Implement it
+//! for your own variant types. Polymorphic `Vec`s are encoded with a length prefix,
+//! and can be decoded when they implement `Default`. As zero sized values take no
+//! bytes, a decoded `Vec` of them can't be longer than the remaining input.
+//!
+//! ```rust
+//! nodyn::codec!();
+//!
+//! nodyn::nodyn! {
+//!     #[derive(Debug, PartialEq)]
+//!     enum Value {
+//!         i32,
+//!         String,
+//!     }
+//!     vec;
+//!     impl codec;
+//! }
+//!
+//! let mut out = Vec::new();
+//! Value::from("hi".to_string()).encode(&mut out);
+//! assert_eq!(out, [1, 2, b'h', b'i']);
+//! assert_eq!(Value::decode(&out), Ok((Value::String("hi".to_string()), 4)));
+//! assert_eq!(Value::decode(&[7]), Err(NodynDecodeError::UnknownTag(7)));
+//!
+//! let values = value_vec![-1, 1];
+//! out.clear();
+//! values.encode(&mut out);
+//! assert_eq!(out, [2, 0, 1, 0, 2]);
+//! assert_eq!(ValueVec::decode(&out).unwrap().0, values);
+//! ```
+//!
+//! ### Oneof Structs (with `oneof`)
+//!
+//! The `oneof` feature generates a struct with an `Option` field for each variant,
+//! like the structs generated for a protobuf `oneof`. The fields are named after the
+//! variants in `snake_case`, as raw identifiers for keywords like `r#box`, or with a
+//! `_` suffix for `crate_`, `self_`, `super_` and `Self_`. The struct has the
+//! `#[derive]` attributes of the enum and `Default`. `From<Enum>` sets the field of the variant, and
+//! `TryFrom<EnumOneOf>` fails with `EnumOneOfError` unless exactly one field is set.
+//!
+//! ```rust
+//! nodyn::nodyn! {
+//!     #[derive(Debug, PartialEq)]
+//!     enum Value {
+//!         i32,
+//!         String,
+//!     }
+//!     impl oneof;
+//! }
+//!
+//! let oneof = ValueOneOf::from(Value::from(7));
+//! assert_eq!(oneof, ValueOneOf { i32: Some(7), string: None });
+//! assert_eq!(Value::try_from(oneof), Ok(Value::I32(7)));
+//!
+//! let both = ValueOneOf { i32: Some(7), string: Some("a".to_string()) };
+//! assert_eq!(Value::try_from(both), Err(ValueOneOfError::Multiple(vec!["i32", "string"])));
+//! assert_eq!(Value::try_from(ValueOneOf::default()), Err(ValueOneOfError::Empty));
+//! ```
+//!
+//! ### C Interop (with `ffi`)
+//!
+//! The `ffi` feature generates a `#[repr(C)]` struct `EnumFfi` to pass the values to C:
+//! the `u32` tag followed by a `#[repr(C)]` union `EnumFfiUnion` of the variant types.
+//! `to_ffi` and the `unsafe` `from_ffi` convert between them, and `EnumFfi::C_HEADER`
+//! defines the tags as C macros. Without `#[tag = n]` the variant positions are the tags.
+//!
+//! The variant types must be `Copy` and FFI-safe, and the enum can't be generic;
+//! other types are compile errors. As an `EnumFfi` may come from C, its tag is
+//! checked: `kind()` and `from_ffi` return `None` for an unknown tag. `from_ffi`
+//! can't check that the tag matches the union field that was set; the caller must
+//! ensure that, as it holds for values created with `to_ffi`. Reading the union
+//! generates `unsafe` code in your crate, which is rejected by
+//! `#![forbid(unsafe_code)]`. Using `impl ffi` is the opt-in to it.
+//!
+//! ```rust
+//! nodyn::nodyn! {
+//!     #[derive(Debug, Clone, Copy, PartialEq)]
+//!     enum Value {
+//!         i32,
+//!         f64,
+//!     }
+//!     impl ffi;
+//! }
+//!
+//! extern "C" fn identity(value: ValueFfi) -> ValueFfi {
+//!     value
+//! }
+//!
+//! let ffi = identity(Value::from(2.5).to_ffi());
+//! assert_eq!(ffi.kind(), Some(ValueKind::F64));
+//! // SAFETY: `ffi` was created by `to_ffi`
+//! assert_eq!(unsafe { Value::from_ffi(ffi) }, Some(Value::F64(2.5)));
+//! assert!(ValueFfi::C_HEADER.contains("#define VALUE_F64 1u"));
+//! ```
+//!
 //! ### Introspection Methods (with `introspection`)
 //!
 //! Enable type introspection with the `introspection` feature to query variant information:
@@ -249,6 +488,33 @@
 //! assert_eq!(val.type_name(), "i32");
 //! ```
 //!
+//! With `impl introspection`, but not the deprecated cargo feature, the enum
+//! also bridges to [`Any`](std::any::Any): `from_any` turns a `Box<dyn Any>`
+//! into the enum by trying each variant type, returning the box if none
+//! matched. Variant types with generic parameters or lifetimes other than
+//! `'static` are not tried.
+//! `as_any` returns the wrapped value as `&dyn Any` and `value_type_id` its
+//! `TypeId`; both require the enum to be `'static`.
+//!
+//! ```rust
+//! use std::any::{Any, TypeId};
+//!
+//! nodyn::nodyn! {
+//!     #[derive(Debug, PartialEq)]
+//!     enum Value { i32, String }
+//!     impl introspection;
+//! }
+//!
+//! let plugin_value: Box<dyn Any> = Box::new(42);
+//! let value = Value::from_any(plugin_value).unwrap();
+//! assert_eq!(value, Value::I32(42));
+//! assert_eq!(value.value_type_id(), TypeId::of::<i32>());
+//! assert_eq!(value.as_any().downcast_ref::<i32>(), Some(&42));
+//!
+//! let unknown: Box<dyn Any> = Box::new(1.5);
+//! assert!(Value::from_any(unknown).is_err());
+//! ```
+//!
 //! ### Type Checking and Conversion Methods (with `is_as`)
 //!
 //! The `is_as` feature generates methods like `is_*` and `try_as_*`
@@ -274,6 +540,277 @@
 //!
 //! Note: `*_ref()` and `*_mut()` methods are not generated for variants that wrap references.
 //!
+//! The `combinators` feature adds `Option`-like methods for each variant:
+//! `unwrap_*`, `expect_*(msg)`, `map_*(f)`, `and_then_*(f)`, `into_*_or(default)`
+//! and `replace_*(value)`. The panic messages of `unwrap_*` and `expect_*` name the
+//! type of the wrapped value:
+//!
+//! ```rust
+//! nodyn::nodyn! {
+//!     #[derive(Debug, PartialEq)]
+//!     enum Container { String, Vec<u8> }
+//!     impl combinators;
+//! }
+//!
+//! let container: Container = "hello".to_string().into();
+//! let container = container.map_string(|s| s.to_uppercase());
+//! assert_eq!(container.into_vec_u8_or(Vec::new()), Vec::<u8>::new());
+//!
+//! let mut container = Container::from(vec![1u8]);
+//! let previous = container.replace_string("world".to_string());
+//! assert_eq!(previous.expect_vec_u8("bytes"), vec![1u8]);
+//! assert_eq!(container.unwrap_string(), "world");
+//! ```
+//!
+//! For generic code, `impl is_as` (but not the deprecated cargo feature) also
+//! generates a trait named after the enum,
+//! like `ContainerMember`, which is implemented by every variant type.
+//! It is used by the generic methods `is::<T>()`, `get::<T>()`,
+//! `get_mut::<T>()` and `take::<T>()`, and by `iter_of::<T>()`,
+//! `iter_of_mut::<T>()`, `first_of::<T>()`, `first_of_mut::<T>()` and
+//! `count_of::<T>()` of a polymorphic `Vec`:
+//!
+//! ```rust
+//! nodyn::nodyn! {
+//!     #[derive(Debug, PartialEq)]
+//!     enum Container { String, Vec<u8> }
+//!     vec;
+//!     impl is_as;
+//! }
+//!
+//! fn count<T: ContainerMember>(containers: &ContainerVec) -> usize {
+//!     containers.count_of::<T>()
+//! }
+//!
+//! let container: Container = "hello".to_string().into();
+//! assert!(container.is::<String>());
+//! assert_eq!(container.get::<Vec<u8>>(), None);
+//! let container = container.take::<Vec<u8>>().unwrap_err();
+//! assert_eq!(container.take::<String>(), Ok("hello".to_string()));
+//!
+//! let containers = container_vec!["a".to_string(), vec![1u8], "b".to_string()];
+//! assert_eq!(count::<String>(&containers), 2);
+//! assert_eq!(containers.first_of::<Vec<u8>>(), Some(&vec![1u8]));
+//! ```
+//!
+//! The member trait is sealed: it is implemented only for the variant types,
+//! so unlike `Into<Container>` it is a guarantee that a type has its own variant.
+//! It provides the position of the variant as `INDEX`, `wrap(self)` and
+//! `unwrap_from(value)`:
+//!
+//! ```rust
+//! nodyn::nodyn! {
+//!     #[derive(Debug, PartialEq)]
+//!     enum Container { String, Vec<u8> }
+//!     impl is_as;
+//! }
+//!
+//! fn store<T: ContainerMember>(containers: &mut Vec<Container>, value: T) -> usize {
+//!     containers.push(value.wrap());
+//!     T::INDEX
+//! }
+//!
+//! let mut containers = Vec::new();
+//! assert_eq!(store(&mut containers, vec![1u8]), 1);
+//! assert_eq!(Vec::<u8>::unwrap_from(containers.remove(0)), Ok(vec![1u8]));
+//! ```
+//!
+//! ### Cross-Variant Comparison (with `compare`)
+//!
+//! Deriving `PartialOrd` orders values by variant first, so `Value::I32(3) < Value::F64(1.0)`.
+//! The `compare` feature instead implements `PartialEq` and `PartialOrd` by comparing
+//! values of the same variant directly and promoting mixed variants using
+//! [`#[into(T)]`](#intot-attribute). Pairs that can't be promoted are never equal
+//! and `partial_cmp` returns `None`. Promotion clones the value, so the
+//! promoted types must implement `Clone`. Don't derive `PartialEq` or `PartialOrd`
+//! together with `compare`; `transparent(Eq)` and `transparent(Ord)` are rejected
+//! with it.
+//!
+//! ```rust
+//! nodyn::nodyn! {
+//!     #[derive(Debug)]
+//!     enum Value {
+//!         #[into(f64)]
+//!         i32,
+//!         f64,
+//!         String,
+//!     }
+//!     impl compare;
+//! }
+//!
+//! assert!(Value::I32(3) > Value::F64(1.0));
+//! assert_eq!(Value::I32(42), Value::F64(42.0));
+//! assert_eq!(Value::I32(1).partial_cmp(&Value::String("a".to_string())), None);
+//! ```
+//!
+//! A polymorphic vec picks up `compare` like a derived `PartialOrd`, so
+//! `is_sorted` and `dedup` are generated.
+//!
+//! ### Equality with Variant Types
+//!
+//! When the enum derives or delegates `PartialEq`, or uses `compare`, `PartialEq<T>`
+//! for the enum and `PartialEq<Enum>` for `T` are implemented for each variant
+//! type `T`. A value only equals its own variant, unless `compare` is used, then
+//! the same promotion rules apply as when comparing two enums.
+//!
+//! ```rust
+//! nodyn::nodyn! {
+//!     #[derive(Debug, PartialEq)]
+//!     enum Value { i32, String }
+//! }
+//!
+//! let value = Value::from(42);
+//! assert_eq!(value, 42);
+//! assert_eq!(42, value);
+//! assert_ne!(value, "42".to_string());
+//! ```
+//!
+//! ### Parsing (with `FromStr`)
+//!
+//! The `FromStr` feature implements [`FromStr`](std::str::FromStr) for the enum by
+//! trying `<T as FromStr>::from_str` for each variant type `T` in declaration order.
+//! The first variant that parses successfully is returned. Use `#[parse(priority = n)]`
+//! to try a variant earlier (higher priorities first, the default is 0) or
+//! `#[parse(skip)]` to never try it.
+//!
+//! If no variant can be parsed, an `EnumNameParseError` is returned. Its
+//! `errors` method returns the name of each attempted type with its error message.
+//!
+//! ```rust
+//! nodyn::nodyn! {
+//!     #[derive(Debug, PartialEq)]
+//!     enum Value {
+//!         i32,
+//!         bool,
+//!         #[parse(priority = -1)]
+//!         String,
+//!         #[parse(skip)]
+//!         f64,
+//!     }
+//!     impl FromStr;
+//! }
+//!
+//! assert_eq!("42".parse::<Value>(), Ok(Value::I32(42)));
+//! assert_eq!("true".parse::<Value>(), Ok(Value::Bool(true)));
+//! assert_eq!("3.14".parse::<Value>(), Ok(Value::String("3.14".to_string())));
+//! ```
+//!
+//! `FromStr` also generates `parse_as(type_name, s)`, which only parses `s` as the
+//! variant with the given type name. Type names are the same as returned by
+//! `types()` and `type_name()` from [`introspection`](#introspection-methods-with-introspection),
+//! so a value can be round-tripped through its type name and `Display` output.
+//! For type names that aren't part of the enum, the error's `unknown_type`
+//! method returns the given name, for variants with `#[parse(skip)]` its
+//! `skipped_type` method does.
+//!
+//! ```rust
+//! use std::fmt;
+//!
+//! nodyn::nodyn! {
+//!     #[derive(Debug, PartialEq)]
+//!     enum Value { i32, bool, String }
+//!     impl FromStr introspection;
+//!     impl fmt::Display {
+//!         fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+//!     }
+//! }
+//!
+//! assert_eq!(Value::parse_as("String", "42"), Ok(Value::String("42".to_string())));
+//!
+//! let value = Value::Bool(true);
+//! let parsed = Value::parse_as(value.type_name(), &value.to_string());
+//! assert_eq!(parsed, Ok(value));
+//! assert_eq!(Value::parse_as("u8", "1").unwrap_err().unknown_type(), Some("u8"));
+//! ```
+//!
+//! ### Per-Type Macro (with `for_each_type`)
+//!
+//! The `for_each_type` feature generates a macro named after the enum, like
+//! `value_for_each_type!`, which invokes the macro passed to it with `(Variant, Type)`
+//! for every variant. This runs generic code once per variant type, like
+//! implementing a trait or registering each type. With `enum` after the macro name,
+//! the path of the enum is passed first, which uses the `#[module_path]` attribute
+//! like the [`vec!`-like macro](#a-vec-like-macro).
+//!
+//! ```rust
+//! nodyn::nodyn! {
+//!     enum Value { i32, String }
+//!     impl for_each_type;
+//! }
+//!
+//! trait Describe {
+//!     fn describe() -> &'static str;
+//! }
+//!
+//! macro_rules! describe {
+//!     ($enum:path, $variant:ident, $ty:ty) => {
+//!         impl Describe for $ty {
+//!             fn describe() -> &'static str {
+//!                 concat!(stringify!($enum), "::", stringify!($variant))
+//!             }
+//!         }
+//!     };
+//! }
+//!
+//! value_for_each_type!(describe, enum);
+//! assert_eq!(i32::describe(), "Value::I32");
+//! assert_eq!(String::describe(), "Value::String");
+//! ```
+//!
+//! The generated macro is exported with `#[macro_export]`, the variant types are
+//! passed as written in the enum so they must be in scope where the macro is used.
+//!
+//! ### Matching by Type (with `match_type`)
+//!
+//! The `match_type` feature generates a macro named after the enum, like
+//! `value_match!`, to match on the variant types instead of the generated variant
+//! names. Types are written without lifetimes, arms can have guards and end with
+//! `_` or a binding for the remaining variants. Like in `match`, every arm must be
+//! followed by a comma, except the last and those with a block body. Unknown types
+//! are reported listing the variant types.
+//!
+//! ```rust
+//! nodyn::nodyn! {
+//!     enum Value<'a> { i32, &'a str, Vec<u8> }
+//!     impl match_type;
+//! }
+//!
+//! let value = Value::from("hello");
+//! let len = value_match!(value,
+//!     i32(n) if n < 0 => 0,
+//!     &str(s) => s.len(),
+//!     Vec<u8>(bytes) => {
+//!         bytes.len()
+//!     }
+//!     _ => 1,
+//! );
+//! assert_eq!(len, 5);
+//! ```
+//!
+//! Like the [`vec!`-like macro](#a-vec-like-macro), the macro uses the enum path from
+//! `#[module_path]` when it is set.
+//!
+//! ### Ad-hoc Dispatch (with `dispatch`)
+//!
+//! For one-off operations that don't need a delegated method, the `dispatch`
+//! feature generates a macro named after the enum, like `value_dispatch!`. It
+//! matches on all variants with the same closure-like body in each arm. Prefix
+//! the value with `ref` or `mut` to match on a reference, or with `wrap` to wrap
+//! the result in the variant again:
+//!
+//! ```rust
+//! nodyn::nodyn! {
+//!     #[derive(Debug, PartialEq)]
+//!     enum Value { i32, String }
+//!     impl dispatch;
+//! }
+//!
+//! let mut value = Value::from(21);
+//! assert_eq!(value_dispatch!(ref value, |x| x.to_string()), "21");
+//! value_dispatch!(mut value, |x| *x = x.clone() + &*x);
+//! assert_eq!(value_dispatch!(wrap value, |x| x.clone() + &x), Value::I32(84));
+//! ```
+//!
 //! ## Method and Trait Delegation
 //!
 //! ### Method Delegation
@@ -324,6 +861,232 @@
 //!
 //! See the [JSON Example](#json-example) for a practical application of trait delegation.
 //!
+//! ### Trait Objects (with `as_dyn`)
+//!
+//! For APIs that take trait objects, `impl as_dyn;` generates for each
+//! delegated trait `as_dyn_<trait>(&self) -> &dyn Trait`,
+//! `as_dyn_<trait>_mut(&mut self) -> &mut dyn Trait`, and
+//! `From<Enum> for Box<dyn Trait + 'x>`, where `'x` is any lifetime outlived by
+//! the enum's generics. `<trait>` is the trait's name in snake case.
+//! Use `impl as_dyn(Trait, ..);` to select traits. A trait that, judging by
+//! its trait block, can't be made into a trait object is reported as an error,
+//! for example when a method has no `self` receiver or returns `Self`.
+//! Methods left out of the trait block aren't checked by the macro; the
+//! compiler then reports the trait as not dyn compatible at the `impl Trait` line.
+//!
+//! ```rust
+//! use std::fmt::{self, Display};
+//!
+//! nodyn::nodyn! {
+//!     enum Value { i32, String }
+//!
+//!     impl Display {
+//!         fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+//!     }
+//!
+//!     impl as_dyn;
+//! }
+//!
+//! let value = Value::from(42);
+//! let display: &dyn Display = value.as_dyn_display();
+//! assert_eq!(display.to_string(), "42");
+//!
+//! let boxed: Box<dyn Display> = Value::from("hi".to_string()).into();
+//! assert_eq!(boxed.to_string(), "hi");
+//! ```
+//!
+//! ### Transparent Traits
+//!
+//! Some traits can be implemented without listing their methods using
+//! `impl transparent(Trait, ..)`. These forward straight to the wrapped value.
+//! Don't also derive a transparent trait on the enum.
+//!
+//! `Debug` and `Display` pass the formatter on unchanged, so alternate
+//! `{:#?}` output and width or precision flags apply to the wrapped value.
+//! A variant with `#[display("...")]` is written using that format string
+//! for `Display`, where `{}` or `{value}` is the wrapped value. Using
+//! `#[display]` without `transparent(Display)`, or more than once on a variant,
+//! is an error.
+//!
+//! ```rust
+//! nodyn::nodyn! {
+//!     enum Value {
+//!         i32,
+//!         #[display("{}!")]
+//!         String,
+//!         f64,
+//!     }
+//!     impl transparent(Debug, Display);
+//! }
+//!
+//! assert_eq!(format!("{:?}", Value::I32(42)), "42");
+//! assert_eq!(format!("{:.1}", Value::F64(3.14)), "3.1");
+//! assert_eq!(Value::String("hello".to_string()).to_string(), "hello!");
+//! ```
+//!
+//! `Hash`, `Eq` and `Ord` use only the wrapped value and ignore the
+//! discriminant, so `Value::I32(1)` hashes like `1i32`. This differs from
+//! deriving them, which is why they must be asked for explicitly.
+//! `Eq` also implements `PartialEq` and `Ord` implements `PartialOrd`.
+//! Values of different variants are never equal and are ordered by the
+//! order in which the variants are declared.
+//!
+//! `Borrow<T>` can be added when every variant type implements `Borrow<T>`,
+//! allowing map lookups by `&T`. `Hash`, `Eq` and `Ord` then compare the
+//! borrowed `T`, as `Borrow` requires, so values of different variants can
+//! be equal. An enum of unrelated types like `i32` and `String` can't
+//! implement `Borrow<i32>`, as its `String` variant has no `i32` to lend.
+//!
+//! ```rust
+//! use std::collections::HashMap;
+//!
+//! nodyn::nodyn! {
+//!     enum Name {
+//!         String,
+//!         Box<str>,
+//!     }
+//!     impl transparent(Hash, Eq, Borrow<str>);
+//! }
+//!
+//! let mut ages = HashMap::new();
+//! ages.insert(Name::from("Alice".to_string()), 30);
+//! ages.insert(Name::from(Box::<str>::from("Bob")), 25);
+//! assert_eq!(ages.get("Bob"), Some(&25));
+//! ```
+//!
+//! ### Error Enums
+//!
+//! An error type wrapping other errors can be made with `impl Error;`,
+//! which implements `Display` and `std::error::Error` by forwarding to the
+//! wrapped error. As `From` is implemented for each variant, `?` converts
+//! the wrapped errors. `source()` returns the source of the wrapped error;
+//! with `impl Error(source);` the wrapped error itself is the source. The
+//! message of a variant with `#[context("...")]` is prefixed by its context.
+//! Don't also implement `Display` for the enum; `transparent(Display)` is
+//! rejected together with `impl Error`.
+//!
+//! ```rust
+//! use std::error::Error;
+//! use std::num::ParseIntError;
+//!
+//! nodyn::nodyn! {
+//!     #[derive(Debug)]
+//!     enum AppError {
+//!         #[context("reading config")]
+//!         std::io::Error,
+//!         ParseIntError,
+//!     }
+//!     impl Error(source);
+//! }
+//!
+//! fn parse(s: &str) -> Result<i32, AppError> {
+//!     Ok(s.parse::<i32>()?)
+//! }
+//!
+//! let error = parse("one").unwrap_err();
+//! assert_eq!(error.to_string(), "invalid digit found in string");
+//! assert!(error.source().unwrap().is::<ParseIntError>());
+//!
+//! let error = AppError::from(std::io::Error::other("not found"));
+//! assert_eq!(error.to_string(), "reading config: not found");
+//! ```
+//!
+//! ### Iterators
+//!
+//! An enum whose variants are all iterators with the same `Item` can
+//! implement `Iterator` without a trait block, using
+//! `impl Iterator<Item = Type>;`. Add `+ DoubleEndedIterator`,
+//! `+ ExactSizeIterator` or `+ FusedIterator` for those traits, which all
+//! variant types must implement too. This replaces returning a
+//! `Box<dyn Iterator>` from functions that return different iterators.
+//!
+//! ```rust
+//! use std::iter::Rev;
+//! use std::ops::Range;
+//!
+//! nodyn::nodyn! {
+//!     enum Countdown {
+//!         Range<u32>,
+//!         Rev<Range<u32>>,
+//!     }
+//!     impl Iterator<Item = u32> + DoubleEndedIterator + ExactSizeIterator;
+//! }
+//!
+//! fn count(up: bool) -> Countdown {
+//!     if up { (1..4).into() } else { (1..4).rev().into() }
+//! }
+//!
+//! assert_eq!(count(true).collect::<Vec<_>>(), vec![1, 2, 3]);
+//! assert_eq!(count(false).collect::<Vec<_>>(), vec![3, 2, 1]);
+//! assert_eq!(count(false).len(), 3);
+//! ```
+//!
+//! ### Futures
+//!
+//! Likewise `impl Future<Output = Type>;` implements `Future` for an enum of
+//! futures. The enum is unpinned to poll the wrapped future, so without
+//! `unsafe` code all variant types must be `Unpin`. Use a `Pin<Box<F>>`
+//! variant for futures that aren't, such as `async` blocks.
+//!
+//! Trait methods with a `self: Pin<&mut Self>` or `self: Pin<&Self>` receiver,
+//! like a stream's `poll_next`, are delegated the same way in trait blocks.
+//! Other receivers with a type, like `self: Box<Self>`, are not supported.
+//!
+//! ```rust
+//! use std::future::{Future, Ready};
+//! use std::pin::Pin;
+//!
+//! type Pending = Pin<Box<dyn Future<Output = String>>>;
+//!
+//! nodyn::nodyn! {
+//!     enum Lookup {
+//!         Ready<String>,
+//!         Pending,
+//!     }
+//!     impl Future<Output = String>;
+//! }
+//!
+//! fn lookup(cached: Option<String>) -> Lookup {
+//!     match cached {
+//!         Some(value) => std::future::ready(value).into(),
+//!         None => (Box::pin(async { "fetched".to_string() }) as Pending).into(),
+//!     }
+//! }
+//! ```
+//!
+//! ## Returning `impl Trait`
+//!
+//! A function returning `impl Trait` can only return one type. With the
+//! `#[nodyn::returns]` attribute, each returned value wrapped in `marker!(..)`
+//! becomes a variant of an anonymous enum defined inside the function, which
+//! implements the traits. The variant types are inferred, so they don't need
+//! to be written down.
+//!
+//! The traits are taken from the `impl Trait` return type, or can be given
+//! with `#[nodyn::returns(impl Trait)]`. Supported are `Iterator<Item = T>`,
+//! `DoubleEndedIterator`, `ExactSizeIterator`, `FusedIterator`,
+//! `Future<Output = T>`, `Debug` and `Display`. The traits can't use the
+//! function's generic parameters, and a `marker!` inside a closure is left alone.
+//!
+//! ```rust
+//! use std::fmt::Display;
+//!
+//! #[nodyn::returns]
+//! fn describe(n: i32) -> impl Display {
+//!     if n < 0 {
+//!         return marker!("negative");
+//!     }
+//!     match n {
+//!         0 => marker!('0'),
+//!         _ => marker!(n),
+//!     }
+//! }
+//!
+//! assert_eq!(describe(-1).to_string(), "negative");
+//! assert_eq!(describe(0).to_string(), "0");
+//! assert_eq!(describe(42).to_string(), "42");
+//! ```
+//!
 //! ## Polymorphic `Vec`
 //!
 //! The `vec` feature generates a `Vec<Enum>` wrapper with delegated `Vec`
@@ -510,6 +1273,7 @@
 //! |-------------------|-----------------|-------------|
 //! | `all_*`           | none | Returns `true` if all items are of this variant |
 //! | `any_*`           | none | Returns `true` if any item is of this variant |
+//! | `contains_*`      | `PartialEq` | Returns `true` if an item of this variant equals the value |
 //! | `count_*`         | none | Counts all items of this variant |
 //! | `enumerate_*`     | none | Enumerate items of this variant with their indices |
 //! | `enumerate_*_mut` | none | Enumerate mutable items of this variant with their indices |
@@ -583,7 +1347,7 @@
 //! | [`copy_within`][slice::copy_within] | `Copy` | none; direct delegation |
 //! | [`dedup_by_key`][Vec::dedup_by_key] | none | none; direct delegation |
 //! | [`dedup_by`][Vec::dedup_by] | none | none; direct delegation |
-//! | [`dedup`][Vec::dedup] | `PartialEq` | none; direct delegation |
+//! | [`dedup`][Vec::dedup] | `PartialEq` or `compare` | none; direct delegation |
 //! | [`extend_from_slice`][Vec::extend_from_slice] | `Clone` | none; direct delegation |
 //! | [`extend_from_within`][Vec::extend_from_within] | `Clone` | none; direct delegation |
 //! | [`extract_if`][Vec::extract_if] | none | none; direct delegation |
@@ -598,7 +1362,7 @@
 //! | [`is_empty`][Vec::is_empty] | none | none; direct delegation |
 //! | [`is_sorted_by_key`][slice::is_sorted_by_key] | none | none; direct delegation |
 //! | [`is_sorted_by`][slice::is_sorted_by] | none | none; direct delegation |
-//! | [`is_sorted`][slice::is_sorted] | `PartialOrd` | none; direct delegation |
+//! | [`is_sorted`][slice::is_sorted] | `PartialOrd` or `compare` | none; direct delegation |
 //! | [`iter_mut`][slice::iter_mut] | none | none; direct delegation |
 //! | [`iter`][slice::iter] | none | none; direct delegation |
 //! | [`last_mut`][slice::last_mut] | none | none; direct delegation |
@@ -670,7 +1434,10 @@
 //!
 //! ### Using `impl` (Recommended)
 //!
-//! Specify features within the macro using `impl TryInto`, `impl is_as`, `impl introspection`, or `vec`.
+//! Specify features within the macro using `impl TryInto`, `impl is_as`, `impl introspection`,
+//! `impl compare`, `impl FromStr`, `impl transparent(..)`, `impl Error`, `impl as_dyn`,
+//! `impl for_each_type`, `impl match_type`, `impl dispatch`, `impl combinators`, `impl serde`,
+//! `impl codec`, `impl oneof`, `impl ffi` or `vec`.
 //! These are disabled by default, allowing explicit control.
 //!
 //! ### Using Cargo Features (Deprecated)
@@ -761,9 +1528,12 @@ use proc_macro::TokenStream;
 use proc_macro2::{Ident, Span};
 use syn::{GenericParam, Generics, Lifetime, parse_macro_input};
 
+mod codec;
 mod method_impl;
+mod naming;
 mod nodyn_enum;
 mod optional_impl;
+mod returns;
 mod trait_impl;
 mod variant;
 mod vec_wrapper;
@@ -771,6 +1541,7 @@ mod vec_wrapper;
 pub(crate) use method_impl::MethodImpl;
 pub(crate) use nodyn_enum::NodynEnum;
 pub(crate) use optional_impl::OptionalImpl;
+pub(crate) use returns::Returns;
 pub(crate) use trait_impl::TraitImpl;
 pub(crate) use variant::{Variant, camel_to_snake};
 pub(crate) use vec_wrapper::VecWrapper;
@@ -783,11 +1554,55 @@ pub fn nodyn(input: TokenStream) -> TokenStream {
     TokenStream::from(nodyn_enum.to_token_stream())
 }
 
+/// Returns one of several types from a function as `impl Trait` by wrapping
+/// each `marker!(..)` into an anonymous enum.
+///
+/// See [Returning `impl Trait`](crate#returning-impl-trait).
+#[proc_macro_attribute]
+pub fn returns(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let returns = parse_macro_input!(attr as Returns);
+    let function = parse_macro_input!(item as syn::ItemFn);
+    returns
+        .expand(function)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Defines the `NodynEncode` trait and `NodynDecodeError` used by `impl codec`,
+/// with implementations for primitives, `String`, `Vec<T>`, `Option<T>` and `Box<T>`.
+///
+/// Invoke it once in the module that uses `impl codec`.
+///
+/// See [Binary Codec](crate#binary-codec-with-codec).
+#[proc_macro]
+pub fn codec(input: TokenStream) -> TokenStream {
+    let input = proc_macro2::TokenStream::from(input);
+    if let Some(token) = input.into_iter().next() {
+        return syn::Error::new(token.span(), "`codec!` takes no arguments")
+            .into_compile_error()
+            .into();
+    }
+    codec::codec_tokens().into()
+}
+
 pub(crate) mod keyword {
     syn::custom_keyword!(vec);
     syn::custom_keyword!(TryInto);
     syn::custom_keyword!(is_as);
     syn::custom_keyword!(introspection);
+    syn::custom_keyword!(compare);
+    syn::custom_keyword!(FromStr);
+    syn::custom_keyword!(transparent);
+    syn::custom_keyword!(Error);
+    syn::custom_keyword!(as_dyn);
+    syn::custom_keyword!(for_each_type);
+    syn::custom_keyword!(match_type);
+    syn::custom_keyword!(dispatch);
+    syn::custom_keyword!(combinators);
+    syn::custom_keyword!(serde);
+    syn::custom_keyword!(codec);
+    syn::custom_keyword!(oneof);
+    syn::custom_keyword!(ffi);
 }
 
 /// Extension trait for managing generics in macro code generation.
@@ -1,6 +1,8 @@
 use proc_macro2::{Delimiter, Group, TokenStream, TokenTree};
 use quote::quote;
-use syn::{FnArg, ImplItem, parse::Parse, parse2};
+use syn::{
+    FnArg, GenericArgument, ImplItem, Path, PathArguments, Receiver, Type, parse::Parse, parse2,
+};
 
 use crate::NodynEnum;
 
@@ -37,7 +39,11 @@ impl Parse for MethodImpl {
                         }
                     })
                     .collect();
-                functions.push(parse2::<syn::ImplItemFn>(ts)?);
+                let function = parse2::<syn::ImplItemFn>(ts)?;
+                if let Some(FnArg::Receiver(receiver)) = function.sig.inputs.first() {
+                    check_receiver(receiver)?;
+                }
+                functions.push(function);
             } else {
                 items.push(item);
             }
@@ -47,22 +53,44 @@ impl Parse for MethodImpl {
 }
 
 impl MethodImpl {
-    pub(crate) fn expand_methods_tokens(&self, wrapper: &NodynEnum) -> Vec<TokenStream> {
+    /// Generates the methods of a trait impl, calling the trait method on the wrapped value.
+    ///
+    /// With `trait_path` the calls are fully qualified, otherwise method call
+    /// syntax is used so the wrapped value is auto-dereferenced.
+    ///
+    /// A `self: Pin<&mut Self>` or `self: Pin<&Self>` receiver is unpinned and the
+    /// wrapped value pinned again, which requires the variant types to be `Unpin`.
+    pub(crate) fn expand_methods_tokens(
+        &self,
+        wrapper: &NodynEnum,
+        trait_path: Option<&Path>,
+    ) -> Vec<TokenStream> {
         self.functions
             .iter()
             .map(|f| {
-                if let Some(FnArg::Receiver(_)) = f.sig.inputs.first() {
-                    let arms = wrapper
-                        .variants
-                        .iter()
-                        .map(|v| v.fn_call_arm_tokens(&wrapper.ident, &f.sig.ident, &f.sig.inputs));
+                if let Some(FnArg::Receiver(receiver)) = f.sig.inputs.first() {
+                    let pinned = pinned_mutability(receiver);
+                    let arms = wrapper.variants.iter().map(|v| {
+                        v.trait_call_arm_tokens(
+                            &wrapper.ident,
+                            trait_path,
+                            &f.sig.ident,
+                            &f.sig.inputs,
+                            pinned.is_some(),
+                        )
+                    });
+                    let scrutinee = match pinned {
+                        Some(true) => quote! { ::core::pin::Pin::get_mut(self) },
+                        Some(false) => quote! { ::core::pin::Pin::get_ref(self) },
+                        None => quote! { self },
+                    };
                     let attrs = &f.attrs;
                     let vis = &f.vis;
                     let signature = &f.sig;
                     quote! {
                         #(#attrs)*
                         #vis #signature {
-                            match self {
+                            match #scrutinee {
                                 #(#arms)*
                             }
                         }
@@ -73,4 +101,58 @@ impl MethodImpl {
             })
             .collect::<Vec<_>>()
     }
+
+    /// Returns `true` if a method has a `self: Pin<&mut Self>` or `self: Pin<&Self>` receiver.
+    pub(crate) fn has_pinned_receiver(&self) -> bool {
+        self.functions.iter().any(|f| {
+            matches!(f.sig.inputs.first(), Some(FnArg::Receiver(receiver)) if pinned_mutability(receiver).is_some())
+        })
+    }
+}
+
+/// Returns whether the reference is mutable for a `self: Pin<&mut Self>` or
+/// `self: Pin<&Self>` receiver, and `None` for other receivers.
+fn pinned_mutability(receiver: &Receiver) -> Option<bool> {
+    receiver.colon_token?;
+    let Type::Path(path) = receiver.ty.as_ref() else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Pin" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.first() {
+        Some(GenericArgument::Type(Type::Reference(reference)))
+            if args.args.len() == 1 && is_self(&reference.elem) =>
+        {
+            Some(reference.mutability.is_some())
+        }
+        _ => None,
+    }
+}
+
+/// Checks that a delegated method has a receiver the wrapped value can be passed to.
+fn check_receiver(receiver: &Receiver) -> syn::Result<()> {
+    let supported = match receiver.ty.as_ref() {
+        _ if receiver.colon_token.is_none() => true,
+        Type::Reference(reference) => is_self(&reference.elem),
+        ty => is_self(ty) || pinned_mutability(receiver).is_some(),
+    };
+    if supported {
+        Ok(())
+    } else {
+        Err(syn::Error::new_spanned(
+            receiver,
+            "Unsupported receiver, expected `self`, `&self`, `&mut self`, \
+             `self: Pin<&Self>` or `self: Pin<&mut Self>`",
+        ))
+    }
+}
+
+/// Returns `true` for the type `Self`.
+fn is_self(ty: &Type) -> bool {
+    matches!(ty, Type::Path(path) if path.qself.is_none() && path.path.is_ident("Self"))
 }
@@ -0,0 +1,195 @@
+use proc_macro2::Span;
+use syn::{Attribute, Ident, LitStr};
+
+use crate::camel_to_snake;
+
+/// Naming of generated identifiers, configured with `#[nodyn(..)]` on the enum.
+#[derive(Debug, Clone)]
+pub(crate) struct Naming {
+    /// Pattern of the `is_*` methods (`is = "is_{}"`).
+    pub(crate) is: String,
+    /// Pattern of the `try_as_*` methods, also used for their `_ref` and `_mut`
+    /// versions (`accessor = "try_as_{}"`).
+    pub(crate) accessor: String,
+    /// Pattern of the name of the standard vec wrapper, filled with the enum name
+    /// (`vec = "{}Vec"`).
+    pub(crate) vec: String,
+    /// Patterns of the vec wrapper methods, with `_mut` added for the mutable
+    /// versions (`first = "first_{}"`, `last`, `iter`, `enumerate`, `count`, `all`,
+    /// `any` and `contains` alike).
+    pub(crate) first: String,
+    pub(crate) last: String,
+    pub(crate) iter: String,
+    pub(crate) enumerate: String,
+    pub(crate) count: String,
+    pub(crate) all: String,
+    pub(crate) any: String,
+    pub(crate) contains: String,
+    /// Patterns of the `impl combinators` methods (`unwrap = "unwrap_{}"`, `expect`,
+    /// `map`, `and_then` and `replace` alike, and `into_or = "into_{}_or"`).
+    pub(crate) unwrap: String,
+    pub(crate) expect: String,
+    pub(crate) map: String,
+    pub(crate) and_then: String,
+    pub(crate) into_or: String,
+    pub(crate) replace: String,
+    /// How names are converted to `snake_case` (`case = "snake_acronym"`).
+    pub(crate) case: Case,
+}
+
+/// Conversion of `CamelCase` names to `snake_case`.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub(crate) enum Case {
+    /// Keeps acronyms together: `HTTPResponse` becomes `http_response`.
+    #[default]
+    SnakeAcronym,
+    /// Starts a word at every uppercase letter: `HTTPResponse` becomes `h_t_t_p_response`.
+    Snake,
+}
+
+impl Default for Naming {
+    fn default() -> Self {
+        Self {
+            is: "is_{}".to_string(),
+            accessor: "try_as_{}".to_string(),
+            vec: "{}Vec".to_string(),
+            first: "first_{}".to_string(),
+            last: "last_{}".to_string(),
+            iter: "iter_{}".to_string(),
+            enumerate: "enumerate_{}".to_string(),
+            count: "count_{}".to_string(),
+            all: "all_{}".to_string(),
+            any: "any_{}".to_string(),
+            contains: "contains_{}".to_string(),
+            unwrap: "unwrap_{}".to_string(),
+            expect: "expect_{}".to_string(),
+            map: "map_{}".to_string(),
+            and_then: "and_then_{}".to_string(),
+            into_or: "into_{}_or".to_string(),
+            replace: "replace_{}".to_string(),
+            case: Case::default(),
+        }
+    }
+}
+
+impl Naming {
+    /// Extracts the `#[nodyn(..)]` attributes, returning the naming and the other attributes.
+    pub(crate) fn extract(attrs: Vec<Attribute>) -> syn::Result<(Self, Vec<Attribute>)> {
+        let mut naming = Self::default();
+        let (nodyn, other_attrs): (Vec<_>, Vec<_>) = attrs
+            .into_iter()
+            .partition(|attr| attr.path().is_ident("nodyn"));
+        for attr in nodyn {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("case") {
+                    let value = meta.value()?.parse::<LitStr>()?;
+                    naming.case = match value.value().as_str() {
+                        "snake_acronym" => Case::SnakeAcronym,
+                        "snake" => Case::Snake,
+                        _ => {
+                            return Err(syn::Error::new_spanned(
+                                value,
+                                "expected `\"snake_acronym\"` or `\"snake\"`",
+                            ));
+                        }
+                    };
+                    return Ok(());
+                }
+                let key = meta.path.get_ident().map(ToString::to_string);
+                let Some(pattern) = key.and_then(|key| naming.pattern_mut(&key)) else {
+                    return Err(meta.error(
+                        "expected `is`, `accessor`, `vec`, `case`, a vec method like `first` \
+                         or a combinator like `unwrap`",
+                    ));
+                };
+                let value = meta.value()?.parse::<LitStr>()?;
+                let filled = value.value().replacen("{}", "x", 1);
+                if filled.contains("{}")
+                    || filled == value.value()
+                    || filled.starts_with("r#")
+                    || !is_ident(&filled)
+                {
+                    return Err(syn::Error::new_spanned(
+                        value,
+                        "expected an identifier with one `{}`, like `\"is_{}\"`",
+                    ));
+                }
+                *pattern = value.value();
+                Ok(())
+            })?;
+        }
+        Ok((naming, other_attrs))
+    }
+
+    /// Returns the pattern set with the `#[nodyn(..)]` key, or `None` for other keys.
+    fn pattern_mut(&mut self, key: &str) -> Option<&mut String> {
+        Some(match key {
+            "is" => &mut self.is,
+            "accessor" => &mut self.accessor,
+            "vec" => &mut self.vec,
+            "first" => &mut self.first,
+            "last" => &mut self.last,
+            "iter" => &mut self.iter,
+            "enumerate" => &mut self.enumerate,
+            "count" => &mut self.count,
+            "all" => &mut self.all,
+            "any" => &mut self.any,
+            "contains" => &mut self.contains,
+            "unwrap" => &mut self.unwrap,
+            "expect" => &mut self.expect,
+            "map" => &mut self.map,
+            "and_then" => &mut self.and_then,
+            "into_or" => &mut self.into_or,
+            "replace" => &mut self.replace,
+            _ => return None,
+        })
+    }
+
+    /// Returns the name of the `is_*` method of a variant.
+    pub(crate) fn is_ident(&self, snake: &str) -> Ident {
+        fill(&self.is, snake, Span::call_site())
+    }
+
+    /// Returns the name of the `try_as_*` method of a variant, with `suffix`.
+    pub(crate) fn accessor_ident(&self, snake: &str, suffix: &str) -> Ident {
+        fill(
+            &self.accessor,
+            &format!("{snake}{suffix}"),
+            Span::call_site(),
+        )
+    }
+
+    /// Returns the name of the standard vec wrapper of the enum.
+    pub(crate) fn vec_ident(&self, enum_ident: &Ident) -> Ident {
+        fill(&self.vec, &enum_ident.to_string(), Span::call_site())
+    }
+}
+
+impl Case {
+    /// Converts a `CamelCase` name to `snake_case`.
+    pub(crate) fn to_snake(self, camel: &str) -> String {
+        match self {
+            Self::SnakeAcronym => camel_to_snake(camel),
+            Self::Snake => {
+                let mut snake = String::new();
+                for (i, c) in camel.chars().enumerate() {
+                    if c.is_uppercase() && i > 0 {
+                        snake.push('_');
+                    }
+                    snake.extend(c.to_lowercase());
+                }
+                snake
+            }
+        }
+    }
+}
+
+/// Returns `true` if `name` can be used as an identifier.
+pub(crate) fn is_ident(name: &str) -> bool {
+    syn::parse_str::<Ident>(name).is_ok()
+}
+
+/// Returns the identifier of `pattern` filled with `name`.
+pub(crate) fn fill(pattern: &str, name: &str, span: Span) -> Ident {
+    Ident::new(&pattern.replacen("{}", name, 1), span)
+}
@@ -1,17 +1,22 @@
 use core::option::Option::None; // for analyzer
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
-use proc_macro2::TokenStream;
-use quote::{ToTokens, format_ident, quote};
+use proc_macro2::{Span, TokenStream};
+use quote::{ToTokens, format_ident, quote, quote_spanned};
+use syn::ext::IdentExt;
 use syn::parse::Parse;
 use syn::parse::ParseStream;
 use syn::{
-    Attribute, FnArg, Generics, Ident, Meta, Path, Token, Type, Visibility, WherePredicate,
-    punctuated::Punctuated, spanned::Spanned,
+    Attribute, FnArg, GenericParam, Generics, Ident, Meta, Path, PathArguments, Token, Type,
+    Visibility, WherePredicate, parse_quote, punctuated::Punctuated, spanned::Spanned,
 };
 
-use crate::vec_wrapper::StandardVecWrapper;
-use crate::{MethodImpl, OptionalImpl, TraitImpl, Variant, VecWrapper, keyword};
+use crate::optional_impl::{ErrorSource, SerdeRepr};
+use crate::vec_wrapper::{StandardVecWrapper, is_trait_derived};
+use crate::{
+    GenericsExt, MethodImpl, OptionalImpl, TraitImpl, Variant, VecWrapper, keyword,
+    naming::{Naming, fill},
+};
 
 /// Represents the input for the `nodyn` procedural macro, defining a nodyn enum.
 #[derive(Debug, Clone)]
@@ -30,18 +35,24 @@ pub(crate) struct NodynEnum {
     pub(crate) method_impls: Vec<MethodImpl>,
     /// Trait implementations for the enum.
     pub(crate) trait_impls: Vec<TraitImpl>,
-    /// Enabled features (`TryInto`, `is_as`, `introspection`).
+    /// Enabled features (`TryInto`, `is_as`, `introspection`, `compare`, `FromStr`, `transparent`,
+    /// `Error`, `as_dyn`, `for_each_type`, `match_type`, `dispatch`, `combinators`, `serde`,
+    /// `codec`, `oneof`, `ffi`).
     pub(crate) optional_impl: OptionalImpl,
     /// Wrapper structs for collections (e.g., `Vec`-based structs).
     pub(crate) vec_wrappers: Vec<VecWrapper>,
     /// module path to where the macro is invoked, used for vec wrapper macro
     pub(crate) module_path: Option<Path>,
+    /// Naming of generated identifiers (via `#[nodyn(..)]`).
+    pub(crate) naming: Naming,
 }
 
 impl Parse for NodynEnum {
+    #[allow(clippy::too_many_lines)]
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let attrs = input.call(Attribute::parse_outer)?;
         let (module_path, attrs) = Self::extract_module_path(&attrs);
+        let (naming, attrs) = Naming::extract(attrs)?;
         let visibility = input.parse::<Visibility>()?;
         let _ = input.parse::<syn::token::Enum>()?;
         let ident = input.parse::<Ident>()?;
@@ -49,9 +60,30 @@ impl Parse for NodynEnum {
 
         let content;
         syn::braced!(content in input);
-        let variants = Punctuated::<Variant, Token![,]>::parse_terminated(&content)?
+        let mut variants = Punctuated::<Variant, Token![,]>::parse_terminated(&content)?
             .into_iter()
             .collect::<Vec<_>>();
+        for variant in &mut variants {
+            if variant.snake.is_none() {
+                variant.snake = Some(naming.case.to_snake(&variant.ident.to_string()));
+            }
+        }
+
+        // Ensure unique snake names, used for method and field names
+        let mut existing_snakes = HashMap::new();
+        for variant in &variants {
+            let snake = variant.ident_to_snake();
+            if let Some(other) = existing_snakes.insert(snake.clone(), &variant.ident) {
+                return Err(syn::Error::new_spanned(
+                    &variant.ty,
+                    format!(
+                        "`{}` has the same snake case name `{snake}` as `{other}`, \
+                         rename one with `#[nodyn(snake = \"..\")]`",
+                        variant.ident
+                    ),
+                ));
+            }
+        }
 
         // Ensure unique variant types
         let mut existing_types = HashSet::new();
@@ -77,10 +109,23 @@ impl Parse for NodynEnum {
                 if input.peek(keyword::TryInto)
                     || input.peek(keyword::is_as)
                     || input.peek(keyword::introspection)
+                    || input.peek(keyword::compare)
+                    || input.peek(keyword::FromStr)
+                    || input.peek(keyword::transparent)
+                    || (input.peek(keyword::Error) && !input.peek2(syn::token::Brace))
+                    || input.peek(keyword::as_dyn)
+                    || input.peek(keyword::for_each_type)
+                    || input.peek(keyword::match_type)
+                    || input.peek(keyword::dispatch)
+                    || input.peek(keyword::combinators)
+                    || (input.peek(keyword::serde) && !input.peek2(Token![::]))
+                    || input.peek(keyword::codec)
+                    || input.peek(keyword::oneof)
+                    || input.peek(keyword::ffi)
                 {
-                    features.merge(input.parse::<OptionalImpl>()?);
+                    features.merge(input.parse::<OptionalImpl>()?)?;
                 } else if input.peek(Ident) {
-                    trait_blocks.push(input.parse::<TraitImpl>()?);
+                    trait_blocks.extend(TraitImpl::parse_all(input)?);
                 } else {
                     impl_blocks.push(input.parse::<MethodImpl>()?);
                 }
@@ -94,6 +139,7 @@ impl Parse for NodynEnum {
                         &ident,
                         &generics,
                         &derive_attrs,
+                        &naming,
                     ));
                 } else if let Ok(mut wrapper_struct) = input.parse::<VecWrapper>() {
                     let (attrs, vec_field) = VecWrapper::parse_custom_attrs(attrs);
@@ -116,7 +162,7 @@ impl Parse for NodynEnum {
             }
         }
 
-        Ok(Self {
+        let nodyn_enum = Self {
             attrs,
             visibility,
             // enum_token,
@@ -128,7 +174,15 @@ impl Parse for NodynEnum {
             optional_impl: features,
             vec_wrappers: collection_structs,
             module_path,
-        })
+            naming,
+        };
+        nodyn_enum.dyn_traits()?;
+        nodyn_enum.check_variant_attrs()?;
+        nodyn_enum.check_tags()?;
+        nodyn_enum.check_ffi()?;
+        nodyn_enum.check_meta()?;
+        nodyn_enum.check_serde()?;
+        Ok(nodyn_enum)
     }
 }
 
@@ -136,6 +190,8 @@ impl NodynEnum {
     pub(crate) fn to_token_stream(&self) -> TokenStream {
         let enum_definition = self.enum_definition_tokens();
         let default = self.default_tokens();
+        let tags = self.tag_tokens();
+        let meta = self.meta_tokens();
         let optional = self.optional_tokens();
         let methods = self.method_tokens();
         let traits = self.trait_tokens();
@@ -148,6 +204,8 @@ impl NodynEnum {
         quote! {
             #enum_definition
             #default
+            #tags
+            #meta
             #optional
             #(#methods)*
             #(#traits)*
@@ -175,6 +233,11 @@ impl NodynEnum {
             )
     }
 
+    /// Returns the enum name in `snake_case`, used for generated macros and modules.
+    pub(crate) fn snake_name(&self) -> String {
+        self.naming.case.to_snake(&self.ident.to_string())
+    }
+
     /// Extracts `#[derive]` attributes from the provided attributes.
     fn extract_derive_attrs(attrs: &[Attribute]) -> Vec<Attribute> {
         attrs
@@ -223,6 +286,32 @@ impl NodynEnum {
         }
     }
 
+    /// Returns a `where` clause with the enum's predicates and `ty: bound` for the type
+    /// of each of the given variants.
+    ///
+    /// The predicates are spanned at the types, so an unsatisfied bound is reported there.
+    pub(crate) fn variant_bounds_where_tokens<'a>(
+        &self,
+        variants: impl IntoIterator<Item = &'a Variant>,
+        bound: &TokenStream,
+    ) -> TokenStream {
+        let predicates = self
+            .generics
+            .where_clause
+            .iter()
+            .flat_map(|w| w.predicates.iter().map(ToTokens::to_token_stream))
+            .chain(variants.into_iter().map(|v| {
+                let ty = &v.ty;
+                quote_spanned! {ty.span()=> #ty: #bound}
+            }))
+            .collect::<Vec<_>>();
+        if predicates.is_empty() {
+            TokenStream::new()
+        } else {
+            quote! { where #(#predicates,)* }
+        }
+    }
+
     pub(crate) fn merged_where_tokens(&self, generics: &Generics) -> TokenStream {
         // (Option<&'static str>, Vec<&'a WherePredicate>) {
         let predicates = match (&self.generics.where_clause, &generics.where_clause) {
@@ -257,8 +346,17 @@ impl NodynEnum {
         quote! {where #(#predicates ,)* }
     }
 
-    fn enum_definition_tokens(&self) -> TokenStream {
-        let variants = self.variants.iter().map(Variant::enum_variant_tokens);
+    pub(crate) fn enum_definition_tokens(&self) -> TokenStream {
+        let variants = self.variants.iter().map(|variant| {
+            if self.optional_impl.serde.is_some() {
+                // `#[serde(..)]` is read by `impl serde`, not by a derive
+                let mut variant = variant.clone();
+                variant.attrs.retain(|attr| !attr.path().is_ident("serde"));
+                variant.enum_variant_tokens()
+            } else {
+                variant.enum_variant_tokens()
+            }
+        });
         let attrs = &self.attrs;
         let visibility = &self.visibility;
         let ident = &self.ident;
@@ -292,6 +390,67 @@ impl NodynEnum {
             .collect()
     }
 
+    /// Generates `PartialEq<T> for Enum` and `PartialEq<Enum> for T` for each variant type.
+    ///
+    /// Without `compare` a value only equals its own variant, with `compare` the value
+    /// is converted into the enum first so `#[into]` promotion applies.
+    fn variant_eq_tokens(&self) -> Vec<TokenStream> {
+        let ident = &self.ident;
+        let generics = &self.generics;
+        self.variants
+            .iter()
+            .map(|variant| {
+                let ty = &variant.ty;
+                let variant_ident = &variant.ident;
+                let where_clause = if self.optional_impl.compare {
+                    self.where_and_predicate_tokens(&parse_quote! { #ty: ::core::clone::Clone })
+                } else {
+                    self.where_and_predicate_tokens(&parse_quote! { #ty: ::core::cmp::PartialEq })
+                };
+                let (enum_eq, type_eq) = if self.optional_impl.compare {
+                    (
+                        quote! {
+                            <Self as ::core::cmp::PartialEq>::eq(self, &#ident::from(::core::clone::Clone::clone(other)))
+                        },
+                        quote! {
+                            <#ident #generics as ::core::cmp::PartialEq>::eq(&#ident::from(::core::clone::Clone::clone(self)), other)
+                        },
+                    )
+                } else {
+                    (
+                        quote! {
+                            match self {
+                                #ident::#variant_ident(value) => <#ty as ::core::cmp::PartialEq>::eq(value, other),
+                                _ => false,
+                            }
+                        },
+                        quote! {
+                            match other {
+                                #ident::#variant_ident(value) => <#ty as ::core::cmp::PartialEq>::eq(self, value),
+                                _ => false,
+                            }
+                        },
+                    )
+                };
+                quote! {
+                    impl #generics ::core::cmp::PartialEq<#ty> for #ident #generics #where_clause {
+                        #[allow(unreachable_patterns)]
+                        fn eq(&self, other: &#ty) -> bool {
+                            #enum_eq
+                        }
+                    }
+
+                    impl #generics ::core::cmp::PartialEq<#ident #generics> for #ty #where_clause {
+                        #[allow(unreachable_patterns)]
+                        fn eq(&self, other: &#ident #generics) -> bool {
+                            #type_eq
+                        }
+                    }
+                }
+            })
+            .collect()
+    }
+
     fn try_from_tokens(&self) -> Vec<TokenStream> {
         let ident = &self.ident;
         let generics = &self.generics;
@@ -363,7 +522,7 @@ impl NodynEnum {
             .collect()
     }
 
-    fn trait_tokens(&self) -> Vec<TokenStream> {
+    pub(crate) fn trait_tokens(&self) -> Vec<TokenStream> {
         let wrapper = &self.ident;
         let lt = &self.generics;
         self.trait_impls
@@ -371,9 +530,28 @@ impl NodynEnum {
             .map(|b| {
                 let trait_path = &b.path;
                 let items = &b.block.items;
-                let fns = b.block.expand_methods_tokens(self);
+                let fns = b
+                    .block
+                    .expand_methods_tokens(self, b.qualified.then_some(trait_path));
+                let mut predicates = self
+                    .generics
+                    .where_clause
+                    .iter()
+                    .flat_map(|w| w.predicates.iter().map(ToTokens::to_token_stream))
+                    .collect::<Vec<_>>();
+                if b.bound_variants {
+                    predicates.extend(self.variants.iter().map(|v| {
+                        let ty = &v.ty;
+                        quote! { #ty: #trait_path }
+                    }));
+                }
+                let where_clause = if predicates.is_empty() {
+                    TokenStream::new()
+                } else {
+                    quote! { where #(#predicates,)* }
+                };
                 quote! {
-                    impl #lt #trait_path for #wrapper #lt {
+                    impl #lt #trait_path for #wrapper #lt #where_clause {
                          #(#items)*
                          #(#fns)*
                     }
@@ -382,6 +560,58 @@ impl NodynEnum {
             .collect()
     }
 
+    /// Generates the `Any` bridges of `impl introspection` (`from_any`, `as_any`,
+    /// `value_type_id`), which the deprecated cargo feature doesn't generate.
+    fn any_tokens(&self) -> TokenStream {
+        let ident = &self.ident;
+        let generics = &self.generics;
+        let visibility = &self.visibility;
+        let any_arms = self
+            .variants
+            .iter()
+            .map(|v| v.dyn_arm_tokens(ident, false))
+            .collect::<Vec<_>>();
+        let downcasts = self
+            .variants
+            .iter()
+            .filter(|v| v.is_static(&self.generics))
+            .map(|v| v.downcast_tokens(ident));
+
+        quote! {
+            impl #generics #ident #generics {
+                /// Creates the enum from a boxed `Any` holding one of the variant types,
+                /// returning the box unchanged if it holds another type.
+                ///
+                /// Variant types with generic parameters or non-`'static` lifetimes are not
+                /// tried.
+                #visibility fn from_any(
+                    value: ::std::boxed::Box<dyn ::core::any::Any>,
+                ) -> ::core::result::Result<Self, ::std::boxed::Box<dyn ::core::any::Any>> {
+                    #(#downcasts)*
+                    ::core::result::Result::Err(value)
+                }
+
+                /// Returns the wrapped value as `&dyn Any`.
+                #visibility fn as_any(&self) -> &dyn ::core::any::Any
+                where
+                    Self: 'static,
+                {
+                    match self {
+                        #(#any_arms)*
+                    }
+                }
+
+                /// Returns the `TypeId` of the wrapped value.
+                #visibility fn value_type_id(&self) -> ::core::any::TypeId
+                where
+                    Self: 'static,
+                {
+                    ::core::any::Any::type_id(self.as_any())
+                }
+            }
+        }
+    }
+
     /// Generates type introspection methods (`count`, `types`, `type_name`).
     fn introspection_tokens(&self) -> TokenStream {
         let ident = &self.ident;
@@ -400,6 +630,7 @@ impl NodynEnum {
 
         quote! {
             impl #generics #ident #generics {
+
                 /// Returns the number of variants in the enum.
                 #visibility const fn count() -> usize {
                     #variant_count
@@ -434,14 +665,14 @@ impl NodynEnum {
                 let snake = variant.ident_to_snake();
                 let type_name = variant.type_to_string();
 
-                let is_fn = format_ident!("is_{}", snake);
+                let is_fn = self.naming.is_ident(&snake);
                 let is_doc = format!("Returns `true` if the variant is `{type_name}`.");
                 let is_arms = self
                     .variants
                     .iter()
                     .map(|v| v.is_type_arm_tokens(ident, ty));
 
-                let as_fn = format_ident!("try_as_{}", snake);
+                let as_fn = self.naming.accessor_ident(&snake, "");
                 let as_doc = format!("Converts to `Option<{type_name}>` if possible.");
                 let as_arms = self
                     .variants
@@ -451,12 +682,12 @@ impl NodynEnum {
                 let ref_mut_methods = if matches!(ty, Type::Reference(_)) {
                     quote! {}
                 } else {
-                    let as_ref_fn = format_ident!("try_as_{}_ref", snake);
+                    let as_ref_fn = self.naming.accessor_ident(&snake, "_ref");
                     let as_ref_doc =
                         format!("Returns `Option<&{type_name}>` if the variant is `{type_name}`.");
                     let as_ref_arms = self.variants.iter().map(|v| v.as_ref_arm_tokens(ident, ty));
 
-                    let as_mut_fn = format_ident!("try_as_{}_mut", snake);
+                    let as_mut_fn = self.naming.accessor_ident(&snake, "_mut");
                     let as_mut_doc = format!(
                         "Returns `Option<&mut {type_name}>` if the variant is `{type_name}`."
                     );
@@ -503,172 +734,2280 @@ impl NodynEnum {
             })
             .collect::<syn::Result<Vec<_>>>()?;
 
+        let member = if self.optional_impl.is_as {
+            self.member_tokens()
+        } else {
+            TokenStream::new()
+        };
         Ok(quote! {
             impl #generics #ident #generics {
                 #(#methods)*
             }
+
+            #member
         })
     }
 
-    /// Generates vector accessor methods for a given `Vec` field in a vec wrapper.
-    pub(crate) fn variant_vec_tokens(&self, vec_field: &Ident) -> TokenStream {
-        let methods = self
-            .variants
-            .iter()
-            .map(|v| v.vec_methods_tokens(&self.ident, vec_field));
-        quote! { #(#methods)* }
+    /// Returns the name of the trait implemented by all variant types: `{Enum}Member`.
+    pub(crate) fn member_trait_ident(&self) -> Ident {
+        format_ident!("{}Member", self.ident)
     }
 
-    /// returns a `TokenStream` that is always included
-    fn default_tokens(&self) -> TokenStream {
-        let from = self.from_tokens();
-        quote! {
-            #(#from)*
+    /// Generates the `{Enum}Member` trait, implemented for every variant type,
+    /// and the generic `is`, `get`, `get_mut` and `take` methods using it.
+    /// Only `impl is_as` generates these, not the deprecated feature.
+    ///
+    /// The trait is sealed by a supertrait in a private module, so only the
+    /// variant types implement it, not the types converted with `#[into]`.
+    fn member_tokens(&self) -> TokenStream {
+        let ident = &self.ident;
+        let visibility = &self.visibility;
+        let generics = &self.generics;
+        let (_, ty_generics, _) = self.generics.split_for_impl();
+        let member = self.member_trait_ident();
+        let new_type = self.generics.new_type();
+        let lt = self.generics.new_lifetime();
+        let trait_doc = format!(
+            "Implemented only by the variant types of [`{ident}`], for generic code and the \
+             [`{ident}::is`], [`{ident}::get`], [`{ident}::get_mut`] and [`{ident}::take`] methods."
+        );
+        let sealed = format_ident!("__{}_member", self.snake_name());
+        let mut sealed_generics = self.generics.clone();
+        sealed_generics.where_clause = None;
+        for param in &mut sealed_generics.params {
+            match param {
+                GenericParam::Lifetime(param) => {
+                    param.colon_token = None;
+                    param.bounds.clear();
+                }
+                GenericParam::Type(param) => {
+                    param.colon_token = None;
+                    param.bounds.clear();
+                    param.eq_token = None;
+                    param.default = None;
+                }
+                GenericParam::Const(param) => {
+                    param.eq_token = None;
+                    param.default = None;
+                }
+            }
         }
-    }
 
-    fn optional_tokens(&self) -> TokenStream {
-        if self.optional_impl.none() {
-            // depreciated feature flags only if no features are set
-            #[cfg(feature = "try_into")]
-            eprintln!(
-                "Warning: The `try_into` cargo feature is deprecated. Use `impl TryInto` in the nodyn! macro."
-            );
-            #[cfg(feature = "introspection")]
-            eprintln!(
-                "Warning: The `introspection` cargo feature is deprecated. Use `impl introspection` in the nodyn! macro."
-            );
-            #[cfg(feature = "is_as")]
-            eprintln!(
-                "Warning: The `is_as` cargo feature is deprecated. Use `impl is_as` in the nodyn! macro."
-            );
-            #[cfg(feature = "try_into")]
-            let try_into = self.try_from_tokens();
-            #[cfg(not(feature = "try_into"))]
-            let try_into = Vec::<proc_macro2::TokenStream>::new();
+        let impls = self.variants.iter().enumerate().map(|(index, variant)| {
+            let variant_ident = &variant.ident;
+            let ty = &variant.ty;
+            quote! {
+                impl #generics #sealed::Sealed #ty_generics for #ty {}
 
-            #[cfg(feature = "introspection")]
-            let type_fns = self.introspection_tokens();
-            #[cfg(not(feature = "introspection"))]
-            let type_fns = proc_macro2::TokenStream::new();
+                impl #generics #member #ty_generics for #ty {
+                    const INDEX: usize = #index;
 
-            #[cfg(feature = "is_as")]
-            let is_as_fn = self.is_as_tokens().unwrap();
-            #[cfg(not(feature = "is_as"))]
-            let is_as_fn = proc_macro2::TokenStream::new();
+                    fn wrap(self) -> #ident #ty_generics {
+                        #ident::#variant_ident(self)
+                    }
 
-            quote! {
-                #(#try_into)*
-                #type_fns
-                #is_as_fn
-            }
-        } else {
-            let try_into = if self.optional_impl.try_into {
-                self.try_from_tokens()
-            } else {
-                Vec::new()
-            };
-            let type_fns = if self.optional_impl.introspection {
-                self.introspection_tokens()
-            } else {
-                proc_macro2::TokenStream::new()
-            };
-            let is_as_fn = if self.optional_impl.is_as {
-                self.is_as_tokens().unwrap()
-            } else {
-                proc_macro2::TokenStream::new()
-            };
-            quote! {
-                #(#try_into)*
-                #type_fns
-                #is_as_fn
-            }
-        }
-    }
-}
+                    #[allow(unreachable_patterns)]
+                    fn get<#lt>(value: &#lt #ident #ty_generics) -> ::core::option::Option<&#lt Self> {
+                        match value {
+                            #ident::#variant_ident(value) => ::core::option::Option::Some(value),
+                            _ => ::core::option::Option::None,
+                        }
+                    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use syn::parse_str;
+                    #[allow(unreachable_patterns)]
+                    fn get_mut<#lt>(value: &#lt mut #ident #ty_generics) -> ::core::option::Option<&#lt mut Self> {
+                        match value {
+                            #ident::#variant_ident(value) => ::core::option::Option::Some(value),
+                            _ => ::core::option::Option::None,
+                        }
+                    }
 
-    #[test]
-    fn test_nodyn_enum_parsing() {
-        let input = parse_str::<NodynEnum>(
-            "
-            #[derive(Debug)]
-            pub enum MyEnum {
-                Number(i32),
-                String,
+                    #[allow(unreachable_patterns)]
+                    fn unwrap_from(value: #ident #ty_generics) -> ::core::result::Result<Self, #ident #ty_generics> {
+                        match value {
+                            #ident::#variant_ident(value) => ::core::result::Result::Ok(value),
+                            other => ::core::result::Result::Err(other),
+                        }
+                    }
+                }
             }
-            ",
-        )
-        .unwrap();
-
-        assert_eq!(input.ident.to_string(), "MyEnum");
-        assert_eq!(input.variants.len(), 2);
-        assert_eq!(input.variants[0].ident.to_string(), "Number");
-        assert_eq!(input.variants[0].type_to_string(), "i32");
-        assert_eq!(input.variants[1].ident.to_string(), "String");
-        assert_eq!(input.variants[1].type_to_string(), "String");
-        assert_eq!(input.method_impls.len(), 0);
-        assert_eq!(input.trait_impls.len(), 0);
-        assert_eq!(input.vec_wrappers.len(), 0);
-        assert_eq!(input.attrs.len(), 1); // #[derive(Debug)]
-    }
+        });
 
-    #[test]
-    fn test_duplicate_variant_types() {
-        let result = parse_str::<NodynEnum>(
-            "
-            pub enum MyEnum {
-                Number(i32),
-                Another(i32),
+        quote! {
+            #[doc(hidden)]
+            mod #sealed {
+                pub trait Sealed #sealed_generics {}
             }
-            ",
-        );
-        assert!(result.is_err());
-        assert!(
-            result
-                .unwrap_err()
-                .to_string()
-                .contains("Duplicate variant type")
-        );
-    }
 
-    #[test]
-    fn test_vec_wrapper_parsing() {
-        let input = parse_str::<NodynEnum>(
-            "
-            pub enum MyEnum {
-                Number(i32),
+            #[doc = #trait_doc]
+            #visibility trait #member #generics: #sealed::Sealed #ty_generics + ::core::marker::Sized {
+                /// The position of the variant wrapping this type.
+                const INDEX: usize;
+                /// Wraps the value in its variant.
+                fn wrap(self) -> #ident #ty_generics;
+                /// Returns a reference to the wrapped value if it is of this type.
+                fn get<#lt>(value: &#lt #ident #ty_generics) -> ::core::option::Option<&#lt Self>;
+                /// Returns a mutable reference to the wrapped value if it is of this type.
+                fn get_mut<#lt>(value: &#lt mut #ident #ty_generics) -> ::core::option::Option<&#lt mut Self>;
+                /// Returns the wrapped value if it is of this type, otherwise the enum unchanged.
+                fn unwrap_from(value: #ident #ty_generics) -> ::core::result::Result<Self, #ident #ty_generics>;
             }
-            vec MyEnumVec;
-            ",
-        )
-        .unwrap();
 
-        assert_eq!(input.vec_wrappers.len(), 1);
-        assert_eq!(
-            input.vec_wrappers[0].definition.ident.to_string(),
-            "MyEnumVec"
-        );
-    }
+            #(#impls)*
 
-    #[test]
-    fn test_features_parsing() {
-        let input = parse_str::<NodynEnum>(
-            "
-            pub enum MyEnum {
-                Number(i32),
+            impl #generics #ident #generics {
+                /// Returns `true` if the wrapped value is of type `T`.
+                pub fn is<#new_type: #member #ty_generics>(&self) -> bool {
+                    <#new_type as #member #ty_generics>::get(self).is_some()
+                }
+
+                /// Returns `Option<&T>` if the wrapped value is of type `T`.
+                pub fn get<#new_type: #member #ty_generics>(&self) -> ::core::option::Option<&#new_type> {
+                    <#new_type as #member #ty_generics>::get(self)
+                }
+
+                /// Returns `Option<&mut T>` if the wrapped value is of type `T`.
+                pub fn get_mut<#new_type: #member #ty_generics>(&mut self) -> ::core::option::Option<&mut #new_type> {
+                    <#new_type as #member #ty_generics>::get_mut(self)
+                }
+
+                /// Returns the wrapped value if it is of type `T`, otherwise `Err(self)`.
+                pub fn take<#new_type: #member #ty_generics>(self) -> ::core::result::Result<#new_type, Self> {
+                    <#new_type as #member #ty_generics>::unwrap_from(self)
+                }
             }
-            impl TryInto;
-            ",
-        )
-        .unwrap();
+        }
+    }
 
-        assert!(input.optional_impl.try_into);
-        assert!(!input.optional_impl.is_as);
+    /// Generates the `{enum}_for_each_type!` macro, which invokes a macro with
+    /// `(Variant, Type)` for every variant.
+    fn for_each_type_tokens(&self) -> TokenStream {
+        let ident = &self.ident;
+        let macro_ident = format_ident!("{}_for_each_type", self.snake_name());
+        let macro_enum = if let Some(path) = &self.module_path {
+            quote! { ::#path::#ident }
+        } else {
+            quote! { #ident }
+        };
+        let variants = self
+            .variants
+            .iter()
+            .map(|variant| {
+                let variant_ident = &variant.ident;
+                let ty = &variant.ty;
+                quote! { #variant_ident, #ty }
+            })
+            .collect::<Vec<_>>();
+        quote! {
+            #[macro_export]
+            macro_rules! #macro_ident {
+                ($callback:ident) => {
+                    #($callback!(#variants);)*
+                };
+                ($callback:ident, enum) => {
+                    #($callback!(#macro_enum, #variants);)*
+                };
+            }
+        }
+    }
+
+    /// Generates the `{enum}_match!` macro, which matches on the variant types:
+    /// `value_match!(value, i32(n) => .., &str(s) => .., _ => ..)`.
+    ///
+    /// Each arm is rewritten to the variant, one arm per recursion, and unknown
+    /// types are reported with `compile_error!`. Like in `match`, an arm with a
+    /// block body needs no comma.
+    fn match_type_tokens(&self) -> TokenStream {
+        let ident = &self.ident;
+        let macro_ident = format_ident!("{}_match", self.snake_name());
+        let macro_enum = if let Some(path) = &self.module_path {
+            quote! { ::#path::#ident }
+        } else {
+            quote! { #ident }
+        };
+        let arms = self.variants.iter().map(|variant| {
+            let variant_ident = &variant.ident;
+            let ty = variant.type_without_lifetimes();
+            quote! {
+                (@arms $value:tt [$($arms:tt)*] #ty ($($pat:tt)*) $(if $guard:expr)? => $($rest:tt)*) => {
+                    #macro_ident!(@body $value [
+                        $($arms)* #macro_enum::#variant_ident($($pat)*) $(if $guard)? =>
+                    ] $($rest)*)
+                };
+            }
+        });
+        let types = self
+            .variants
+            .iter()
+            .map(|variant| format!("`{}`", variant.type_without_lifetimes().to_token_stream()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let unknown = format!("`{macro_ident}!` expected one of the types {types} or `_`, found `");
+        quote! {
+            #[macro_export]
+            macro_rules! #macro_ident {
+                #(#arms)*
+                (@arms $value:tt [$($arms:tt)*] _ $(if $guard:expr)? => $($rest:tt)*) => {
+                    #macro_ident!(@body $value [$($arms)* _ $(if $guard)? =>] $($rest)*)
+                };
+                (@arms $value:tt [$($arms:tt)*] $binding:ident $(if $guard:expr)? => $($rest:tt)*) => {
+                    #macro_ident!(@body $value [$($arms)* $binding $(if $guard)? =>] $($rest)*)
+                };
+                (@arms $value:tt [$($arms:tt)*]) => {
+                    match $value {
+                        $($arms)*
+                    }
+                };
+                (@arms $value:tt [$($arms:tt)*] $($other:tt)*) => {
+                    #macro_ident!(@unknown [] $($other)*)
+                };
+                (@body $value:tt [$($arms:tt)*] $body:block , $($rest:tt)*) => {
+                    #macro_ident!(@arms $value [$($arms)* $body] $($rest)*)
+                };
+                (@body $value:tt [$($arms:tt)*] $body:block $($rest:tt)*) => {
+                    #macro_ident!(@arms $value [$($arms)* $body] $($rest)*)
+                };
+                (@body $value:tt [$($arms:tt)*] $body:expr $(, $($rest:tt)*)?) => {
+                    #macro_ident!(@arms $value [$($arms)* $body,] $($($rest)*)?)
+                };
+                // the unknown type is everything up to its pattern, guard or `=>`
+                (@unknown [$($ty:tt)+] ($($pat:tt)*) $($rest:tt)*) => {
+                    #macro_ident!(@unknown [$($ty)+])
+                };
+                (@unknown [$($ty:tt)*] if $($rest:tt)*) => {
+                    #macro_ident!(@unknown [$($ty)*])
+                };
+                (@unknown [$($ty:tt)*] => $($rest:tt)*) => {
+                    #macro_ident!(@unknown [$($ty)*])
+                };
+                (@unknown [$($ty:tt)*] $next:tt $($rest:tt)*) => {
+                    #macro_ident!(@unknown [$($ty)* $next] $($rest)*)
+                };
+                (@unknown [$($ty:tt)*]) => {
+                    ::core::compile_error!(::core::concat!(#unknown, ::core::stringify!($($ty)*), "`"))
+                };
+                ($value:expr, $($arms:tt)*) => {
+                    #macro_ident!(@arms ($value) [] $($arms)*)
+                };
+            }
+        }
+    }
+
+    /// Generates the `{enum}_dispatch!` macro, which matches on all variants with
+    /// the same body in each arm: `value_dispatch!(value, |x| x.to_string())`.
+    ///
+    /// The value is matched as is, or by reference with `ref` or `mut`, and with
+    /// `wrap` the result is wrapped in the variant again.
+    fn dispatch_tokens(&self) -> TokenStream {
+        let ident = &self.ident;
+        let macro_ident = format_ident!("{}_dispatch", self.snake_name());
+        let macro_enum = if let Some(path) = &self.module_path {
+            quote! { ::#path::#ident }
+        } else {
+            quote! { #ident }
+        };
+        let binding = quote! { $x };
+        let arms = self
+            .variants
+            .iter()
+            .map(|variant| variant.expr_arm_tokens(&macro_enum, &binding, &quote! { $body }))
+            .collect::<Vec<_>>();
+        let wrap_arms = self.variants.iter().map(|variant| {
+            let variant_ident = &variant.ident;
+            variant.expr_arm_tokens(
+                &macro_enum,
+                &binding,
+                &quote! { #macro_enum::#variant_ident($body) },
+            )
+        });
+        quote! {
+            #[macro_export]
+            macro_rules! #macro_ident {
+                (ref $value:expr, |$x:pat_param| $body:expr $(,)?) => {
+                    match &$value {
+                        #(#arms)*
+                    }
+                };
+                (mut $value:expr, |$x:pat_param| $body:expr $(,)?) => {
+                    match &mut $value {
+                        #(#arms)*
+                    }
+                };
+                (wrap $value:expr, |$x:pat_param| $body:expr $(,)?) => {
+                    match $value {
+                        #(#wrap_arms)*
+                    }
+                };
+                ($value:expr, |$x:pat_param| $body:expr $(,)?) => {
+                    match $value {
+                        #(#arms)*
+                    }
+                };
+            }
+        }
+    }
+
+    /// Generates `Option`-like methods for each variant (`unwrap_`, `expect_`, `map_`,
+    /// `and_then_`, `into_*_or` and `replace_` by default, see `Naming`).
+    #[allow(clippy::too_many_lines)]
+    fn combinators_tokens(&self) -> TokenStream {
+        let ident = &self.ident;
+        let generics = &self.generics;
+        let visibility = &self.visibility;
+        let new_type = self.generics.new_type();
+        let type_name_arms = self
+            .variants
+            .iter()
+            .map(|v| v.type_as_str_arm_tokens(ident))
+            .collect::<Vec<_>>();
+        let methods = self.variants.iter().map(|variant| {
+            let variant_ident = &variant.ident;
+            let ty = &variant.ty;
+            let snake = variant.ident_to_snake();
+            let type_name = variant.type_to_string();
+
+            let unwrap_fn = fill(&self.naming.unwrap, &snake, Span::call_site());
+            let unwrap_doc = format!(
+                "Returns the `{type_name}` value.\n\n# Panics\n\nPanics if the variant is not `{variant_ident}`."
+            );
+            let unwrap_msg = format!("called `{ident}::{unwrap_fn}()` on a `{{}}` value");
+            let expect_fn = fill(&self.naming.expect, &snake, Span::call_site());
+            let expect_doc = format!(
+                "Returns the `{type_name}` value.\n\n# Panics\n\nPanics with `msg` if the variant is not `{variant_ident}`."
+            );
+            let map_fn = fill(&self.naming.map, &snake, Span::call_site());
+            let map_doc =
+                format!("Maps the `{type_name}` value with `f`, other variants are returned unchanged.");
+            let and_then_fn = fill(&self.naming.and_then, &snake, Span::call_site());
+            let and_then_doc = format!(
+                "Calls `f` with the `{type_name}` value, other variants are returned unchanged."
+            );
+            let or_fn = fill(&self.naming.into_or, &snake, Span::call_site());
+            let or_doc = format!("Returns the `{type_name}` value or `default` for other variants.");
+            let replace_fn = fill(&self.naming.replace, &snake, Span::call_site());
+            let replace_doc = format!("Replaces the enum with `value` as `{variant_ident}`, returning the previous enum.");
+
+            quote! {
+                #[doc = #unwrap_doc]
+                #[track_caller]
+                #[allow(unreachable_patterns)]
+                #visibility fn #unwrap_fn(self) -> #ty {
+                    match self {
+                        #ident::#variant_ident(value) => value,
+                        other => {
+                            let found = match &other {
+                                #(#type_name_arms)*
+                            };
+                            ::core::panic!(#unwrap_msg, found)
+                        }
+                    }
+                }
+
+                #[doc = #expect_doc]
+                #[track_caller]
+                #[allow(unreachable_patterns)]
+                #visibility fn #expect_fn(self, msg: &str) -> #ty {
+                    match self {
+                        #ident::#variant_ident(value) => value,
+                        other => {
+                            let found = match &other {
+                                #(#type_name_arms)*
+                            };
+                            ::core::panic!("{}: found a `{}` value", msg, found)
+                        }
+                    }
+                }
+
+                #[doc = #map_doc]
+                #visibility fn #map_fn<#new_type>(self, f: #new_type) -> Self
+                where
+                    #new_type: ::core::ops::FnOnce(#ty) -> #ty,
+                {
+                    match self {
+                        #ident::#variant_ident(value) => #ident::#variant_ident(f(value)),
+                        #[allow(unreachable_patterns)]
+                        other => other,
+                    }
+                }
+
+                #[doc = #and_then_doc]
+                #visibility fn #and_then_fn<#new_type>(self, f: #new_type) -> Self
+                where
+                    #new_type: ::core::ops::FnOnce(#ty) -> Self,
+                {
+                    match self {
+                        #ident::#variant_ident(value) => f(value),
+                        #[allow(unreachable_patterns)]
+                        other => other,
+                    }
+                }
+
+                #[doc = #or_doc]
+                #visibility fn #or_fn(self, default: #ty) -> #ty {
+                    match self {
+                        #ident::#variant_ident(value) => value,
+                        #[allow(unreachable_patterns)]
+                        _ => default,
+                    }
+                }
+
+                #[doc = #replace_doc]
+                #visibility fn #replace_fn(&mut self, value: #ty) -> Self {
+                    ::core::mem::replace(self, #ident::#variant_ident(value))
+                }
+            }
+        });
+
+        quote! {
+            impl #generics #ident #generics {
+                #(#methods)*
+            }
+        }
+    }
+
+    /// Returns `true` if the variants have a `#[tag = n]`.
+    pub(crate) fn has_tags(&self) -> bool {
+        self.variants.iter().any(|v| v.tag.is_some())
+    }
+
+    /// Returns the tag of each variant: its `#[tag = n]`, or its position when the
+    /// variants have no tags.
+    fn tags(&self) -> Vec<u32> {
+        self.variants
+            .iter()
+            .enumerate()
+            .map(|(index, v)| {
+                v.tag_value()
+                    .unwrap_or_else(|| u32::try_from(index).unwrap_or(u32::MAX))
+            })
+            .collect()
+    }
+
+    /// Checks that `#[display]` and `#[context]` are only used with the features reading them.
+    fn check_variant_attrs(&self) -> syn::Result<()> {
+        let mut errors = Vec::new();
+        for variant in &self.variants {
+            if let Some(display) = &variant.display {
+                if !self.optional_impl.transparent.display {
+                    errors.push(syn::Error::new_spanned(
+                        display,
+                        "`#[display]` requires `impl transparent(Display)`",
+                    ));
+                }
+            }
+            if let Some(context) = &variant.context {
+                if self.optional_impl.error.is_none() {
+                    errors.push(syn::Error::new_spanned(
+                        context,
+                        "`#[context]` requires `impl Error`",
+                    ));
+                }
+            }
+        }
+        match errors.into_iter().reduce(|mut all, error| {
+            all.combine(error);
+            all
+        }) {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    /// Checks the `#[serde(..)]` attributes of the variants when they are read by `impl serde`.
+    fn check_serde(&self) -> syn::Result<()> {
+        if self.optional_impl.serde.is_none() {
+            return Ok(());
+        }
+        match self
+            .variants
+            .iter()
+            .filter_map(|variant| variant.serde_rename().err())
+            .reduce(|mut all, error| {
+                all.combine(error);
+                all
+            }) {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    /// Checks that either all or no variants have a `#[tag = n]`, and that tags are unique.
+    fn check_tags(&self) -> syn::Result<()> {
+        if !self.has_tags() {
+            return Ok(());
+        }
+        let mut errors = Vec::new();
+        let mut seen = HashMap::new();
+        for variant in &self.variants {
+            let Some(tag) = &variant.tag else {
+                errors.push(syn::Error::new_spanned(
+                    &variant.ty,
+                    format!(
+                        "missing `#[tag = n]` on `{}`, all variants need a tag when one has",
+                        variant.ident
+                    ),
+                ));
+                continue;
+            };
+            if let Some(other) = seen.insert(variant.tag_value(), &variant.ident) {
+                errors.push(syn::Error::new_spanned(
+                    tag,
+                    format!("duplicate `#[tag = {tag}]`, already used by `{other}`"),
+                ));
+            }
+        }
+        match errors.into_iter().reduce(|mut all, error| {
+            all.combine(error);
+            all
+        }) {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the keys of `#[meta(key = "value")]`, in the order they first appear.
+    fn meta_keys(&self) -> Vec<&Ident> {
+        let mut keys = Vec::<&Ident>::new();
+        for (key, _) in self.variants.iter().flat_map(|v| &v.meta) {
+            if !keys.contains(&key) {
+                keys.push(key);
+            }
+        }
+        keys
+    }
+
+    /// Checks that every variant sets all `#[meta]` keys.
+    fn check_meta(&self) -> syn::Result<()> {
+        let keys = self.meta_keys();
+        let mut errors = Vec::new();
+        for variant in &self.variants {
+            for key in keys.iter().filter(|key| variant.meta_value(key).is_none()) {
+                errors.push(syn::Error::new_spanned(
+                    &variant.ty,
+                    format!(
+                        "missing `#[meta({key} = \"..\")]` on `{}`, all variants need the keys used by one",
+                        variant.ident
+                    ),
+                ));
+            }
+        }
+        match errors.into_iter().reduce(|mut all, error| {
+            all.combine(error);
+            all
+        }) {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    /// Generates a `const fn` for each `#[meta]` key on the enum and the `{Enum}Kind`,
+    /// and the `META` table with the values of each kind.
+    fn meta_tokens(&self) -> TokenStream {
+        let keys = self.meta_keys();
+        if keys.is_empty() {
+            return TokenStream::new();
+        }
+        let ident = &self.ident;
+        let generics = &self.generics;
+        let visibility = &self.visibility;
+        let kind = format_ident!("{}Kind", ident);
+        let key_count = keys.len();
+        let variant_count = self.variants.len();
+        let key_names = keys.iter().map(ToString::to_string);
+        let variants = self.variants.iter().map(|v| &v.ident).collect::<Vec<_>>();
+        let values = |key: &Ident| {
+            self.variants
+                .iter()
+                .map(|v| v.meta_value(key))
+                .collect::<Vec<_>>()
+        };
+        let kind_fns = keys.iter().map(|key| {
+            let doc = format!("Returns the `{key}` set with `#[meta]`.");
+            let values = values(key);
+            quote! {
+                #[doc = #doc]
+                #visibility const fn #key(self) -> &'static str {
+                    match self {
+                        #(Self::#variants => #values,)*
+                    }
+                }
+            }
+        });
+        let enum_fns = keys.iter().map(|key| {
+            let doc = format!("Returns the `{key}` of the current variant, set with `#[meta]`.");
+            quote! {
+                #[doc = #doc]
+                #visibility const fn #key(&self) -> &'static str {
+                    self.kind().#key()
+                }
+            }
+        });
+        let rows = self.variants.iter().map(|v| {
+            let variant = &v.ident;
+            let row = keys.iter().map(|key| v.meta_value(key));
+            quote! { (#kind::#variant, [#(#row),*]) }
+        });
+
+        quote! {
+            impl #kind {
+                #(#kind_fns)*
+            }
+
+            impl #generics #ident #generics {
+                /// The keys of `#[meta]`, in the order of the values in `META`.
+                #visibility const META_KEYS: [&'static str; #key_count] = [#(#key_names),*];
+
+                /// The `#[meta]` values of each kind, in declaration order.
+                #visibility const META: [(#kind, [&'static str; #key_count]); #variant_count] = [#(#rows),*];
+
+                #(#enum_fns)*
+            }
+        }
+    }
+
+    /// Checks that an enum with `impl ffi` has no generic parameters, which can't be
+    /// represented in C.
+    fn check_ffi(&self) -> syn::Result<()> {
+        if self.optional_impl.ffi && !self.generics.params.is_empty() {
+            return Err(syn::Error::new_spanned(
+                &self.generics,
+                "`impl ffi` doesn't support generic enums",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Generates `tag`, `kind` and `TAGS` for the enum and the `{Enum}Kind` enum
+    /// with `from_tag`, when the variants have a `#[tag = n]` or for `impl ffi`.
+    /// With only `#[meta]`, just `kind` and the `{Enum}Kind` enum are generated.
+    fn tag_tokens(&self) -> TokenStream {
+        let ident = &self.ident;
+        let generics = &self.generics;
+        let visibility = &self.visibility;
+        let kind = format_ident!("{}Kind", ident);
+        let variants = self.variants.iter().map(|v| &v.ident).collect::<Vec<_>>();
+        let kind_fn = quote! {
+            /// Returns the kind of the current variant.
+            #visibility const fn kind(&self) -> #kind {
+                match self {
+                    #(#ident::#variants(_) => #kind::#variants,)*
+                }
+            }
+        };
+        if !self.has_tags() && !self.optional_impl.ffi {
+            if self.meta_keys().is_empty() {
+                return TokenStream::new();
+            }
+            // `#[meta]` alone only needs the kind to look up its values
+            let kind_doc = format!("The variants of [`{ident}`] without their values.");
+            return quote! {
+                #[doc = #kind_doc]
+                #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+                #visibility enum #kind {
+                    #(#variants,)*
+                }
+
+                impl #generics #ident #generics {
+                    #kind_fn
+                }
+            };
+        }
+        let kind_doc = format!(
+            "The variants of [`{ident}`] without their values, with their tags as discriminant."
+        );
+        let variant_count = self.variants.len();
+        let tags = self.tags();
+
+        quote! {
+            #[doc = #kind_doc]
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+            #[repr(u32)]
+            #visibility enum #kind {
+                #(#variants = #tags,)*
+            }
+
+            impl #kind {
+                /// Returns the tag of the variant.
+                #visibility const fn tag(self) -> u32 {
+                    self as u32
+                }
+
+                /// Returns the variant with `tag`, or `None` for an unknown tag.
+                #visibility const fn from_tag(tag: u32) -> ::core::option::Option<Self> {
+                    match tag {
+                        #(#tags => ::core::option::Option::Some(Self::#variants),)*
+                        _ => ::core::option::Option::None,
+                    }
+                }
+            }
+
+            impl #generics #ident #generics {
+                /// The tags of the variants, in declaration order.
+                #visibility const TAGS: [u32; #variant_count] = [#(#tags),*];
+
+                /// Returns the tag of the current variant: its `#[tag = n]`, or its position
+                /// without tags.
+                #visibility const fn tag(&self) -> u32 {
+                    self.kind().tag()
+                }
+
+                #kind_fn
+            }
+        }
+    }
+
+    /// Generates `Serialize` and `Deserialize` for the enum (via `impl serde`), using the
+    /// user's `serde` crate.
+    ///
+    /// `Deserialize` is derived for a copy of the enum inside `deserialize`, which is
+    /// converted into the enum, so buffering for untagged and adjacent variants is left
+    /// to `serde`.
+    #[allow(clippy::too_many_lines)]
+    fn serde_tokens(&self) -> TokenStream {
+        let Some(repr) = self.optional_impl.serde else {
+            return TokenStream::new();
+        };
+        let ident = &self.ident;
+        let enum_name = ident.to_string();
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let predicates = where_clause.map(|clause| &clause.predicates);
+        let types = self.variants.iter().map(|v| &v.ty).collect::<Vec<_>>();
+        let variants = self.variants.iter().map(|v| &v.ident).collect::<Vec<_>>();
+        let names = self
+            .variants
+            .iter()
+            .map(Variant::serde_name)
+            .collect::<Vec<_>>();
+
+        let serialize_arms = self.variants.iter().enumerate().map(|(index, variant)| {
+            let variant_ident = &variant.ident;
+            let name = &names[index];
+            let index = u32::try_from(index).unwrap_or(u32::MAX);
+            let body = match repr {
+                SerdeRepr::Tagged => quote! {
+                    ::serde::Serializer::serialize_newtype_variant(serializer, #enum_name, #index, #name, value)
+                },
+                SerdeRepr::Untagged => quote! {
+                    ::serde::Serialize::serialize(value, serializer)
+                },
+                SerdeRepr::Adjacent => quote! {{
+                    let mut state = ::serde::Serializer::serialize_struct(serializer, #enum_name, 2)?;
+                    ::serde::ser::SerializeStruct::serialize_field(&mut state, "tag", #name)?;
+                    ::serde::ser::SerializeStruct::serialize_field(&mut state, "content", value)?;
+                    ::serde::ser::SerializeStruct::end(state)
+                }},
+            };
+            quote! { #ident::#variant_ident(value) => #body, }
+        });
+
+        let de = self.generics.new_lifetime();
+        let mut de_generics = self.generics.clone();
+        // borrowed variant types, like `&'a str`, need `'de: 'a`
+        let lifetimes = self
+            .generics
+            .lifetimes()
+            .map(|param| &param.lifetime)
+            .collect::<Vec<_>>();
+        if lifetimes.is_empty() {
+            de_generics.params.insert(0, parse_quote! { #de });
+        } else {
+            de_generics
+                .params
+                .insert(0, parse_quote! { #de: #(#lifetimes)+* });
+        }
+        let (de_impl_generics, _, _) = de_generics.split_for_impl();
+        let shadow = format_ident!("{}Serde", ident);
+        let shadow_generics = &self.generics;
+        let container_attr = match repr {
+            SerdeRepr::Tagged => quote! {},
+            SerdeRepr::Untagged => quote! { #[serde(untagged)] },
+            SerdeRepr::Adjacent => quote! { #[serde(tag = "tag", content = "content")] },
+        };
+        let bound = types
+            .iter()
+            .map(|ty| format!("{}: ::serde::Deserialize<'de>", ty.to_token_stream()))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        quote! {
+            impl #impl_generics ::serde::Serialize for #ident #ty_generics
+            where
+                #predicates
+                #(#types: ::serde::Serialize,)*
+            {
+                fn serialize<S: ::serde::Serializer>(
+                    &self,
+                    serializer: S,
+                ) -> ::core::result::Result<S::Ok, S::Error> {
+                    match self {
+                        #(#serialize_arms)*
+                    }
+                }
+            }
+
+            impl #de_impl_generics ::serde::Deserialize<#de> for #ident #ty_generics
+            where
+                #predicates
+                #(#types: ::serde::Deserialize<#de>,)*
+            {
+                fn deserialize<D: ::serde::Deserializer<#de>>(
+                    deserializer: D,
+                ) -> ::core::result::Result<Self, D::Error> {
+                    #[derive(::serde::Deserialize)]
+                    #[serde(bound(deserialize = #bound))]
+                    #container_attr
+                    enum #shadow #shadow_generics {
+                        #(
+                            #[serde(rename = #names)]
+                            #variants(#types),
+                        )*
+                    }
+
+                    ::core::result::Result::Ok(
+                        match <#shadow #ty_generics as ::serde::Deserialize<#de>>::deserialize(deserializer)? {
+                            #(#shadow::#variants(value) => #ident::#variants(value),)*
+                        }
+                    )
+                }
+            }
+        }
+    }
+
+    /// Implements `NodynEncode` for the enum (via `impl codec`) and adds `encode` and
+    /// `decode` methods.
+    ///
+    /// A value is encoded as its `#[tag = n]`, or the variant position without tags,
+    /// as a varint followed by the wrapped value. The trait is defined by `nodyn::codec!()`
+    /// and must be in scope.
+    fn codec_tokens(&self) -> TokenStream {
+        let ident = &self.ident;
+        let visibility = &self.visibility;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let predicates = where_clause.map(|clause| &clause.predicates);
+        let types = self.variants.iter().map(|v| &v.ty).collect::<Vec<_>>();
+        let variants = self.variants.iter().map(|v| &v.ident).collect::<Vec<_>>();
+        let tags = self.tags();
+
+        quote! {
+            impl #impl_generics NodynEncode for #ident #ty_generics
+            where
+                #predicates
+                #(#types: NodynEncode,)*
+            {
+                fn encode(&self, out: &mut ::std::vec::Vec<u8>) {
+                    match self {
+                        #(
+                            Self::#variants(value) => {
+                                NodynEncode::encode(&#tags, out);
+                                NodynEncode::encode(value, out);
+                            }
+                        )*
+                    }
+                }
+
+                fn decode(input: &[u8]) -> ::core::result::Result<(Self, usize), NodynDecodeError> {
+                    let (tag, read) = <u32 as NodynEncode>::decode(input)?;
+                    let rest = input.get(read..).unwrap_or_default();
+                    match tag {
+                        #(
+                            #tags => {
+                                let (value, len) = <#types as NodynEncode>::decode(rest)?;
+                                ::core::result::Result::Ok((Self::#variants(value), read + len))
+                            }
+                        )*
+                        tag => ::core::result::Result::Err(NodynDecodeError::UnknownTag(tag)),
+                    }
+                }
+            }
+
+            impl #impl_generics #ident #ty_generics
+            where
+                #predicates
+                #(#types: NodynEncode,)*
+            {
+                /// Appends the encoded value to `out`.
+                #visibility fn encode(&self, out: &mut ::std::vec::Vec<u8>) {
+                    <Self as NodynEncode>::encode(self, out);
+                }
+
+                /// Decodes a value from the start of `input`, returning it with the number
+                /// of bytes read.
+                ///
+                /// # Errors
+                ///
+                /// Returns an error if `input` ends early, has an unknown tag or doesn't
+                /// hold a valid value.
+                #visibility fn decode(input: &[u8]) -> ::core::result::Result<(Self, usize), NodynDecodeError> {
+                    <Self as NodynEncode>::decode(input)
+                }
+            }
+        }
+    }
+
+    /// Generates the `{enum}OneOf` struct (via `impl oneof`), with an `Option` field per
+    /// variant like a protobuf `oneof`, and conversions from and to the enum.
+    ///
+    /// The struct has the `#[derive]` attributes of the enum. Converting it into the
+    /// enum fails with `{enum}OneOfError` unless exactly one field is set.
+    #[allow(clippy::too_many_lines)]
+    fn oneof_tokens(&self) -> TokenStream {
+        let ident = &self.ident;
+        let visibility = &self.visibility;
+        let generics = &self.generics;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let oneof = format_ident!("{}OneOf", ident);
+        let error = format_ident!("{}OneOfError", ident);
+        let oneof_doc = format!("[`{ident}`] as a struct with an optional field for each variant.");
+        let error_doc = format!("The error returned when converting a `{oneof}` into a `{ident}`.");
+        let derive_attrs = Self::extract_derive_attrs(&self.attrs);
+        let default = if is_trait_derived(&self.attrs, "Default") {
+            TokenStream::new()
+        } else {
+            quote! { #[derive(Default)] }
+        };
+        let types = self.variants.iter().map(|v| &v.ty).collect::<Vec<_>>();
+        let variants = self.variants.iter().map(|v| &v.ident).collect::<Vec<_>>();
+        let fields = self
+            .variants
+            .iter()
+            .map(Variant::snake_field_ident)
+            .collect::<Vec<_>>();
+        let names = fields
+            .iter()
+            .map(|field| field.unraw().to_string())
+            .collect::<Vec<_>>();
+        let field_docs = types
+            .iter()
+            .map(|ty| format!("Set when the value is a `{}`.", ty.to_token_stream()));
+        let from_arms = variants.iter().zip(&fields).map(|(variant, field)| {
+            let others = fields.iter().filter(|other| *other != field);
+            quote! {
+                #ident::#variant(value) => Self {
+                    #field: ::core::option::Option::Some(value),
+                    #(#others: ::core::option::Option::None,)*
+                },
+            }
+        });
+
+        quote! {
+            #[doc = #oneof_doc]
+            #default
+            #(#derive_attrs)*
+            #visibility struct #oneof #generics #where_clause {
+                #(
+                    #[doc = #field_docs]
+                    #visibility #fields: ::core::option::Option<#types>,
+                )*
+            }
+
+            #[doc = #error_doc]
+            #[derive(Debug, Clone, PartialEq, Eq)]
+            #visibility enum #error {
+                /// No field is set.
+                Empty,
+                /// Several fields are set, with their names.
+                Multiple(::std::vec::Vec<&'static str>),
+            }
+
+            impl ::core::fmt::Display for #error {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    match self {
+                        Self::Empty => write!(f, "no field of `{}` is set", stringify!(#oneof)),
+                        Self::Multiple(names) => write!(
+                            f,
+                            "only one field of `{}` can be set, found `{}`",
+                            stringify!(#oneof),
+                            names.join("`, `"),
+                        ),
+                    }
+                }
+            }
+
+            impl ::std::error::Error for #error {}
+
+            impl #impl_generics ::core::convert::From<#ident #ty_generics> for #oneof #ty_generics #where_clause {
+                fn from(value: #ident #ty_generics) -> Self {
+                    match value {
+                        #(#from_arms)*
+                    }
+                }
+            }
+
+            impl #impl_generics ::core::convert::TryFrom<#oneof #ty_generics> for #ident #ty_generics #where_clause {
+                type Error = #error;
+
+                fn try_from(value: #oneof #ty_generics) -> ::core::result::Result<Self, Self::Error> {
+                    let set = [#(value.#fields.is_some().then_some(#names),)*]
+                        .into_iter()
+                        .flatten()
+                        .collect::<::std::vec::Vec<_>>();
+                    if set.len() > 1 {
+                        return ::core::result::Result::Err(#error::Multiple(set));
+                    }
+                    #(
+                        if let ::core::option::Option::Some(value) = value.#fields {
+                            return ::core::result::Result::Ok(Self::#variants(value));
+                        }
+                    )*
+                    ::core::result::Result::Err(#error::Empty)
+                }
+            }
+        }
+    }
+
+    /// Generates the `#[repr(C)]` `{Enum}Ffi` struct (via `impl ffi`), holding the
+    /// `{Enum}Kind` as tag and a `#[repr(C)]` union of the variant types, with `to_ffi`
+    /// and `from_ffi` and a C header listing the tags.
+    ///
+    /// As the struct can be created by C code or in the enum's module, `from_ffi` is
+    /// `unsafe` and requires a tag matching the union field that was set. Each variant
+    /// type is checked to be `Copy` and FFI-safe, with the check spanned at the type.
+    #[allow(clippy::too_many_lines)]
+    fn ffi_tokens(&self) -> TokenStream {
+        let ident = &self.ident;
+        let visibility = &self.visibility;
+        let kind = format_ident!("{}Kind", ident);
+        let ffi = format_ident!("{}Ffi", ident);
+        let union = format_ident!("{}FfiUnion", ident);
+        let ffi_doc =
+            format!("[`{ident}`] as a C compatible tagged union, created with `{ident}::to_ffi`.");
+        let union_doc = format!("The values of the variants of [`{ident}`], selected by the tag.");
+        let types = self.variants.iter().map(|v| &v.ty).collect::<Vec<_>>();
+        let variants = self.variants.iter().map(|v| &v.ident).collect::<Vec<_>>();
+        let fields = self
+            .variants
+            .iter()
+            .map(Variant::snake_field_ident)
+            .collect::<Vec<_>>();
+        // the bound is checked at the type, which reports each type that isn't `Copy` once
+        let copy_bounds = types
+            .iter()
+            .map(|ty| quote_spanned! {ty.span()=> #ty: ::core::marker::Copy});
+        let copy_where = quote! { where #(#copy_bounds,)* };
+        // each type is checked in a `#[repr(C)]` struct, as arrays can be fields but not
+        // parameters, and the error is reported at the type
+        let ffi_checks = types
+            .iter()
+            .enumerate()
+            .map(|(i, ty)| format_ident!("__NodynFfiCheck{}", i, span = ty.span()))
+            .collect::<Vec<_>>();
+        let prefix = self.snake_name().to_uppercase();
+        let header = std::iter::once(format!("/* Tags of `{ident}`. */\n"))
+            .chain(self.variants.iter().zip(self.tags()).map(|(variant, tag)| {
+                format!(
+                    "#define {prefix}_{} {tag}u\n",
+                    variant.ident_to_snake().to_uppercase()
+                )
+            }))
+            .collect::<String>();
+
+        quote! {
+            #[doc = #ffi_doc]
+            #[repr(C)]
+            #visibility struct #ffi {
+                tag: u32,
+                value: #union,
+            }
+
+            #[doc = #union_doc]
+            #[repr(C)]
+            #visibility union #union {
+                #(#fields: ::core::mem::ManuallyDrop<#types>,)*
+            }
+
+            impl ::core::clone::Clone for #ffi #copy_where {
+                fn clone(&self) -> Self {
+                    *self
+                }
+            }
+
+            impl ::core::marker::Copy for #ffi #copy_where {}
+
+            impl ::core::clone::Clone for #union #copy_where {
+                fn clone(&self) -> Self {
+                    *self
+                }
+            }
+
+            impl ::core::marker::Copy for #union #copy_where {}
+
+            #[deny(improper_ctypes_definitions)]
+            const _: () = {
+                #(
+                    #[allow(dead_code)]
+                    #[repr(C)]
+                    struct #ffi_checks(#types);
+                )*
+                extern "C" fn __nodyn_assert_ffi_safe(#(_: #ffi_checks),*) {}
+            };
+
+            impl #ffi {
+                /// Defines the tags as C macros.
+                #visibility const C_HEADER: &'static str = #header;
+
+                /// Returns the stored tag, which isn't checked when the value comes from C.
+                #visibility const fn tag(&self) -> u32 {
+                    self.tag
+                }
+
+                /// Returns the kind of the value, or `None` for an unknown tag.
+                #visibility const fn kind(&self) -> ::core::option::Option<#kind> {
+                    #kind::from_tag(self.tag)
+                }
+            }
+
+            impl #ident #copy_where {
+                /// Converts the value into its C compatible representation.
+                #visibility const fn to_ffi(&self) -> #ffi {
+                    match *self {
+                        #(
+                            Self::#variants(value) => #ffi {
+                                tag: #kind::#variants.tag(),
+                                value: #union { #fields: ::core::mem::ManuallyDrop::new(value) },
+                            },
+                        )*
+                    }
+                }
+
+                /// Converts the C compatible representation back into the value, or
+                /// returns `None` for an unknown tag.
+                ///
+                /// # Safety
+                ///
+                /// The value of `ffi` must be an initialized value of the variant its tag
+                /// selects, as ensured for values created with `to_ffi`.
+                #visibility const unsafe fn from_ffi(ffi: #ffi) -> ::core::option::Option<Self> {
+                    match #kind::from_tag(ffi.tag) {
+                        #(
+                            ::core::option::Option::Some(#kind::#variants) => ::core::option::Option::Some(
+                                Self::#variants(::core::mem::ManuallyDrop::into_inner(unsafe { ffi.value.#fields })),
+                            ),
+                        )*
+                        ::core::option::Option::None => ::core::option::Option::None,
+                    }
+                }
+            }
+
+            impl ::core::convert::From<#ident> for #ffi #copy_where {
+                fn from(value: #ident) -> Self {
+                    value.to_ffi()
+                }
+            }
+        }
+    }
+
+    /// Generates `PartialEq` and `PartialOrd` implementations that compare across variants.
+    ///
+    /// Values of the same variant are compared directly, mixed variants are
+    /// promoted using the `#[into]` attribute. Pairs that cannot be promoted
+    /// are unequal and unordered.
+    fn compare_tokens(&self) -> TokenStream {
+        let ident = &self.ident;
+        let generics = &self.generics;
+        let eq_arms = self.variants.iter().flat_map(|left| {
+            self.variants
+                .iter()
+                .map(move |right| left.eq_arm_tokens(right, ident))
+        });
+        let cmp_arms = self.variants.iter().flat_map(|left| {
+            self.variants
+                .iter()
+                .map(move |right| left.partial_cmp_arm_tokens(right, ident))
+        });
+
+        quote! {
+            impl #generics ::core::cmp::PartialEq for #ident #generics {
+                fn eq(&self, other: &Self) -> bool {
+                    match (self, other) {
+                        #(#eq_arms)*
+                    }
+                }
+            }
+
+            impl #generics ::core::cmp::PartialOrd for #ident #generics {
+                fn partial_cmp(&self, other: &Self) -> ::core::option::Option<::core::cmp::Ordering> {
+                    match (self, other) {
+                        #(#cmp_arms)*
+                    }
+                }
+            }
+        }
+    }
+
+    /// Generates `FromStr` and `parse_as` for the enum and the error type they return.
+    ///
+    /// Variants are tried in order of their `#[parse(priority = n)]`, highest first,
+    /// keeping declaration order for equal priorities. Variants with `#[parse(skip)]`
+    /// are never tried. If all variants fail, the error lists each attempted type
+    /// with its error message.
+    ///
+    /// `parse_as` only tries the variant with the given type name, using the same
+    /// names as `types()` and `type_name()` from `introspection`.
+    #[allow(clippy::wrong_self_convention, clippy::too_many_lines)]
+    fn from_str_tokens(&self) -> TokenStream {
+        let ident = &self.ident;
+        let generics = &self.generics;
+        let visibility = &self.visibility;
+        let error_ident = format_ident!("{}ParseError", ident);
+        let error_doc = format!("The error returned when parsing a `{ident}` fails.");
+        let mut variants = self
+            .variants
+            .iter()
+            .filter(|v| !v.parse_skip)
+            .collect::<Vec<_>>();
+        variants.sort_by_key(|v| ::core::cmp::Reverse(v.parse_priority));
+        let attempts = variants.iter().map(|v| v.from_str_attempt_tokens(ident));
+        let parse_as_arms = variants.iter().map(|v| {
+            let type_name = v.type_to_string();
+            let attempt = v.from_str_attempt_tokens(ident);
+            quote! { #type_name => { #attempt } }
+        });
+        let skipped_arms = self.variants.iter().filter(|v| v.parse_skip).map(|v| {
+            let type_name = v.type_to_string();
+            quote! {
+                #type_name => {
+                    return ::core::result::Result::Err(#error_ident {
+                        errors,
+                        unknown_type: ::core::option::Option::None,
+                        skipped_type: ::core::option::Option::Some(#type_name),
+                    });
+                }
+            }
+        });
+
+        quote! {
+            #[doc = #error_doc]
+            #[derive(Debug, Clone, PartialEq, Eq)]
+            #visibility struct #error_ident {
+                errors: ::std::vec::Vec<(&'static str, ::std::string::String)>,
+                unknown_type: ::core::option::Option<::std::string::String>,
+                skipped_type: ::core::option::Option<&'static str>,
+            }
+
+            impl #error_ident {
+                /// Returns each attempted type name with the error message it returned.
+                #visibility fn errors(&self) -> &[(&'static str, ::std::string::String)] {
+                    &self.errors
+                }
+
+                /// Returns the type name given to `parse_as` if it isn't a type of the enum.
+                #visibility fn unknown_type(&self) -> ::core::option::Option<&str> {
+                    self.unknown_type.as_deref()
+                }
+
+                /// Returns the type name given to `parse_as` if its variant has `#[parse(skip)]`.
+                #visibility fn skipped_type(&self) -> ::core::option::Option<&str> {
+                    self.skipped_type
+                }
+            }
+
+            impl ::core::fmt::Display for #error_ident {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    if let ::core::option::Option::Some(type_name) = &self.unknown_type {
+                        return write!(f, "`{}` is not a type of `{}`", type_name, stringify!(#ident));
+                    }
+                    if let ::core::option::Option::Some(type_name) = self.skipped_type {
+                        return write!(f, "`{}` is skipped when parsing `{}`", type_name, stringify!(#ident));
+                    }
+                    write!(f, "no variant of `{}` could be parsed", stringify!(#ident))?;
+                    for (i, (type_name, error)) in self.errors.iter().enumerate() {
+                        let separator = if i == 0 { ": " } else { "; " };
+                        write!(f, "{separator}{type_name}: {error}")?;
+                    }
+                    Ok(())
+                }
+            }
+
+            impl ::std::error::Error for #error_ident {}
+
+            impl #generics ::core::str::FromStr for #ident #generics {
+                type Err = #error_ident;
+                #[allow(unreachable_code)]
+                fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                    let mut errors = ::std::vec::Vec::new();
+                    #(#attempts)*
+                    ::core::result::Result::Err(#error_ident {
+                        errors,
+                        unknown_type: ::core::option::Option::None,
+                        skipped_type: ::core::option::Option::None,
+                    })
+                }
+            }
+
+            impl #generics #ident #generics {
+                /// Parses `s` as the variant with the given type name.
+                ///
+                /// The type name is the same as returned by `type_name()`.
+                #[allow(unreachable_code)]
+                #visibility fn parse_as(type_name: &str, s: &str) -> ::core::result::Result<Self, #error_ident> {
+                    let mut errors = ::std::vec::Vec::new();
+                    match type_name {
+                        #(#parse_as_arms)*
+                        #(#skipped_arms)*
+                        _ => {
+                            return ::core::result::Result::Err(#error_ident {
+                                errors,
+                                unknown_type: ::core::option::Option::Some(::std::string::ToString::to_string(type_name)),
+                                skipped_type: ::core::option::Option::None,
+                            });
+                        }
+                    }
+                    ::core::result::Result::Err(#error_ident {
+                        errors,
+                        unknown_type: ::core::option::Option::None,
+                        skipped_type: ::core::option::Option::None,
+                    })
+                }
+            }
+        }
+    }
+
+    /// Generates the traits enabled with `transparent`, forwarding to the wrapped value.
+    ///
+    /// `Debug` and `Display` pass the formatter on unchanged, so flags like `{:#?}`
+    /// and width or precision apply to the wrapped value. A variant with
+    /// `#[display("..")]` is written with that format string instead.
+    fn transparent_tokens(&self) -> TokenStream {
+        let ident = &self.ident;
+        let generics = &self.generics;
+        let debug = if self.optional_impl.transparent.debug {
+            let arms = self
+                .variants
+                .iter()
+                .map(|v| v.fmt_arm_tokens(ident, &parse_quote! { ::core::fmt::Debug }, None));
+            let where_clause =
+                self.variant_bounds_where_tokens(&self.variants, &quote! { ::core::fmt::Debug });
+            quote! {
+                impl #generics ::core::fmt::Debug for #ident #generics #where_clause {
+                    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                        match self {
+                            #(#arms)*
+                        }
+                    }
+                }
+            }
+        } else {
+            TokenStream::new()
+        };
+        let display = if self.optional_impl.transparent.display {
+            let arms = self.variants.iter().map(|v| {
+                v.fmt_arm_tokens(
+                    ident,
+                    &parse_quote! { ::core::fmt::Display },
+                    v.display.as_ref(),
+                )
+            });
+            // a `#[display("..")]` format may use other traits of the value
+            let where_clause = self.variant_bounds_where_tokens(
+                self.variants.iter().filter(|v| v.display.is_none()),
+                &quote! { ::core::fmt::Display },
+            );
+            quote! {
+                impl #generics ::core::fmt::Display for #ident #generics #where_clause {
+                    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                        match self {
+                            #(#arms)*
+                        }
+                    }
+                }
+            }
+        } else {
+            TokenStream::new()
+        };
+        let hash_eq_ord = self.transparent_hash_eq_ord_tokens();
+        quote! {
+            #debug
+            #display
+            #hash_eq_ord
+        }
+    }
+
+    /// Generates the transparent `Hash`, `Eq` and `Ord` and `Borrow<T>` impls.
+    ///
+    /// Without `Borrow<T>`, values of different variants are never equal and are
+    /// ordered by variant declaration order. With `Borrow<T>`, all three compare the
+    /// borrowed `T`, as `Borrow` requires them to behave identically.
+    #[allow(clippy::too_many_lines)]
+    fn transparent_hash_eq_ord_tokens(&self) -> TokenStream {
+        let ident = &self.ident;
+        let generics = &self.generics;
+        let transparent = &self.optional_impl.transparent;
+        let mut tokens = TokenStream::new();
+        // with `Borrow<T>` the impls compare the borrowed `T`, otherwise the variant types
+        let where_clause = |bound: TokenStream| {
+            if let Some(target) = &transparent.borrow {
+                self.variant_bounds_where_tokens(
+                    &self.variants,
+                    &quote! { ::core::borrow::Borrow<#target> },
+                )
+            } else {
+                self.variant_bounds_where_tokens(&self.variants, &bound)
+            }
+        };
+        if let Some(target) = &transparent.borrow {
+            let arms = self
+                .variants
+                .iter()
+                .map(|v| v.borrow_arm_tokens(ident, target));
+            let where_clause = where_clause(TokenStream::new());
+            tokens.extend(quote! {
+                impl #generics ::core::borrow::Borrow<#target> for #ident #generics #where_clause {
+                    fn borrow(&self) -> &#target {
+                        match self {
+                            #(#arms)*
+                        }
+                    }
+                }
+            });
+        }
+        if transparent.hash {
+            let body = if let Some(target) = &transparent.borrow {
+                quote! {
+                    <#target as ::core::hash::Hash>::hash(
+                        ::core::borrow::Borrow::<#target>::borrow(self),
+                        state,
+                    )
+                }
+            } else {
+                let arms = self.variants.iter().map(|v| v.hash_arm_tokens(ident));
+                quote! { match self { #(#arms)* } }
+            };
+            let where_clause = where_clause(quote! { ::core::hash::Hash });
+            tokens.extend(quote! {
+                impl #generics ::core::hash::Hash for #ident #generics #where_clause {
+                    fn hash<H: ::core::hash::Hasher>(&self, state: &mut H) {
+                        #body
+                    }
+                }
+            });
+        }
+        if transparent.eq {
+            let body = if let Some(target) = &transparent.borrow {
+                quote! {
+                    <#target as ::core::cmp::PartialEq>::eq(
+                        ::core::borrow::Borrow::<#target>::borrow(self),
+                        ::core::borrow::Borrow::<#target>::borrow(other),
+                    )
+                }
+            } else {
+                let arms = self.variants.iter().map(|v| {
+                    v.transparent_cmp_arm_tokens(
+                        ident,
+                        &parse_quote! { ::core::cmp::PartialEq },
+                        &format_ident!("eq"),
+                    )
+                });
+                quote! {
+                    #[allow(unreachable_patterns)]
+                    match (self, other) {
+                        #(#arms)*
+                        _ => false,
+                    }
+                }
+            };
+            let eq_where = where_clause(quote! { ::core::cmp::PartialEq });
+            let where_clause = where_clause(quote! { ::core::cmp::Eq });
+            tokens.extend(quote! {
+                impl #generics ::core::cmp::PartialEq for #ident #generics #eq_where {
+                    fn eq(&self, other: &Self) -> bool {
+                        #body
+                    }
+                }
+
+                impl #generics ::core::cmp::Eq for #ident #generics #where_clause {}
+            });
+        }
+        if transparent.ord {
+            let body = if let Some(target) = &transparent.borrow {
+                quote! {
+                    <#target as ::core::cmp::Ord>::cmp(
+                        ::core::borrow::Borrow::<#target>::borrow(self),
+                        ::core::borrow::Borrow::<#target>::borrow(other),
+                    )
+                }
+            } else {
+                let arms = self.variants.iter().map(|v| {
+                    v.transparent_cmp_arm_tokens(
+                        ident,
+                        &parse_quote! { ::core::cmp::Ord },
+                        &format_ident!("cmp"),
+                    )
+                });
+                let index_arms = self
+                    .variants
+                    .iter()
+                    .enumerate()
+                    .map(|(i, v)| v.index_arm_tokens(ident, i));
+                quote! {
+                    #[allow(unreachable_patterns)]
+                    match (self, other) {
+                        #(#arms)*
+                        _ => {
+                            let index = |value: &Self| match value {
+                                #(#index_arms)*
+                            };
+                            ::core::cmp::Ord::cmp(&index(self), &index(other))
+                        }
+                    }
+                }
+            };
+            let where_clause = where_clause(quote! { ::core::cmp::Ord });
+            tokens.extend(quote! {
+                impl #generics ::core::cmp::PartialOrd for #ident #generics #where_clause {
+                    fn partial_cmp(&self, other: &Self) -> ::core::option::Option<::core::cmp::Ordering> {
+                        ::core::option::Option::Some(::core::cmp::Ord::cmp(self, other))
+                    }
+                }
+
+                impl #generics ::core::cmp::Ord for #ident #generics #where_clause {
+                    fn cmp(&self, other: &Self) -> ::core::cmp::Ordering {
+                        #body
+                    }
+                }
+            });
+        }
+        tokens
+    }
+
+    /// Returns the delegated traits to bridge to trait objects (via `impl as_dyn`).
+    ///
+    /// Errors if a selected trait isn't delegated or can't be made into a trait object.
+    fn dyn_traits(&self) -> syn::Result<Vec<&TraitImpl>> {
+        let Some(selected) = &self.optional_impl.as_dyn else {
+            return Ok(Vec::new());
+        };
+        let traits = if selected.is_empty() {
+            self.trait_impls.iter().collect::<Vec<_>>()
+        } else {
+            selected
+                .iter()
+                .map(|ident| {
+                    self.trait_impls
+                        .iter()
+                        .find(|t| t.trait_ident() == Some(ident))
+                        .ok_or_else(|| {
+                            syn::Error::new(
+                                ident.span(),
+                                format!("`{ident}` is not delegated with an `impl {ident}` block"),
+                            )
+                        })
+                })
+                .collect::<syn::Result<Vec<_>>>()?
+        };
+        for t in &traits {
+            if let Some(reason) = t.dyn_incompatibility() {
+                let path = &t.path;
+                return Err(syn::Error::new_spanned(
+                    path,
+                    format!(
+                        "`impl as_dyn` can't make `{}` into a trait object, {reason}{}",
+                        path.to_token_stream().to_string().replace(' ', ""),
+                        if selected.is_empty() {
+                            "; select the traits with `impl as_dyn(Trait, ..)`"
+                        } else {
+                            ""
+                        },
+                    ),
+                ));
+            }
+        }
+        Ok(traits)
+    }
+
+    /// Generates `as_dyn_<trait>`, `as_dyn_<trait>_mut` and `From<Enum> for Box<dyn Trait>`
+    /// for the traits selected with `impl as_dyn`.
+    ///
+    /// Associated types are bound in the trait object type; the iterator presets
+    /// without their own take `Item` from the `Iterator` impl.
+    fn as_dyn_tokens(&self) -> TokenStream {
+        let ident = &self.ident;
+        let generics = &self.generics;
+        let visibility = &self.visibility;
+        let iterator_types = self
+            .trait_impls
+            .iter()
+            .find(|t| t.trait_ident().is_some_and(|i| i == "Iterator"))
+            .map(TraitImpl::associated_types)
+            .unwrap_or_default();
+        // a trait object takes a single lifetime bound, so the boxed object gets
+        // one that every lifetime and type parameter of the enum outlives
+        let mut box_generics = self.generics.clone();
+        let (lifetime, box_where) = if self.generics.params.is_empty() {
+            (None, None)
+        } else {
+            box_generics.params.insert(0, parse_quote! { '__nodyn });
+            let bounds = self
+                .generics
+                .lifetimes()
+                .map(|l| &l.lifetime)
+                .map(|l| quote! { #l: '__nodyn })
+                .chain(self.generics.type_params().map(|t| {
+                    let t = &t.ident;
+                    quote! { #t: '__nodyn }
+                }))
+                .chain(
+                    self.generics
+                        .where_clause
+                        .iter()
+                        .flat_map(|w| w.predicates.iter().map(ToTokens::to_token_stream)),
+                );
+            (
+                Some(quote! { + '__nodyn }),
+                Some(quote! { where #(#bounds,)* }),
+            )
+        };
+        let bridges = self.dyn_traits().unwrap_or_default().into_iter().map(|t| {
+            let mut path = t.path.clone();
+            let mut bindings = t.associated_types();
+            if bindings.is_empty()
+                && t.trait_ident().is_some_and(|i| {
+                    i == "DoubleEndedIterator" || i == "ExactSizeIterator" || i == "FusedIterator"
+                })
+            {
+                bindings.clone_from(&iterator_types);
+            }
+            if !bindings.is_empty() {
+                if let Some(segment) = path.segments.last_mut() {
+                    if let PathArguments::AngleBracketed(args) = &mut segment.arguments {
+                        args.args.extend(bindings);
+                    } else {
+                        segment.arguments =
+                            PathArguments::AngleBracketed(parse_quote! { <#(#bindings),*> });
+                    }
+                }
+            }
+            let snake = self
+                .naming
+                .case
+                .to_snake(&t.trait_ident().map(ToString::to_string).unwrap_or_default());
+            let fn_as_dyn = format_ident!("as_dyn_{}", snake);
+            let fn_as_dyn_mut = format_ident!("as_dyn_{}_mut", snake);
+            let arms = self
+                .variants
+                .iter()
+                .map(|v| v.dyn_arm_tokens(ident, false))
+                .collect::<Vec<_>>();
+            let box_arms = self.variants.iter().map(|v| v.dyn_arm_tokens(ident, true));
+            // spanned at the trait, so methods missing from the block that make it
+            // dyn incompatible are reported at the user's `impl Trait`
+            let dyn_path = quote_spanned! {t.path.span()=> dyn #path};
+            quote! {
+                impl #generics #ident #generics {
+                    /// Returns the wrapped value as a trait object.
+                    #visibility fn #fn_as_dyn(&self) -> &#dyn_path {
+                        match self {
+                            #(#arms)*
+                        }
+                    }
+
+                    /// Returns the wrapped value as a mutable trait object.
+                    #visibility fn #fn_as_dyn_mut(&mut self) -> &mut #dyn_path {
+                        match self {
+                            #(#arms)*
+                        }
+                    }
+                }
+
+                impl #box_generics ::core::convert::From<#ident #generics>
+                    for ::std::boxed::Box<#dyn_path #lifetime>
+                #box_where
+                {
+                    fn from(value: #ident #generics) -> Self {
+                        match value {
+                            #(#box_arms)*
+                        }
+                    }
+                }
+            }
+        });
+        quote! { #(#bridges)* }
+    }
+
+    /// Generates `Display` and `Error` for an error enum (via `impl Error`).
+    ///
+    /// `Display` writes the wrapped error's message, prefixed by the variant's
+    /// `#[context("...")]`. The deprecated `description` keeps its default.
+    fn error_tokens(&self) -> TokenStream {
+        let Some(source) = self.optional_impl.error else {
+            return TokenStream::new();
+        };
+        let ident = &self.ident;
+        let generics = &self.generics;
+        let display_arms = self
+            .variants
+            .iter()
+            .map(|v| v.error_display_arm_tokens(ident));
+        let source_arms = self
+            .variants
+            .iter()
+            .map(|v| v.error_source_arm_tokens(ident, source == ErrorSource::Variant));
+        quote! {
+            impl #generics ::core::fmt::Display for #ident #generics {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    match self {
+                        #(#display_arms)*
+                    }
+                }
+            }
+
+            impl #generics ::core::error::Error for #ident #generics {
+                fn source(&self) -> ::core::option::Option<&(dyn ::core::error::Error + 'static)> {
+                    match self {
+                        #(#source_arms)*
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns `true` if the enum implements `PartialEq`, either derived, delegated or via
+    /// `compare` or `transparent(Eq)`.
+    pub(crate) fn has_partial_eq(&self) -> bool {
+        self.optional_impl.compare
+            || self.optional_impl.transparent.eq
+            || is_trait_derived(&self.attrs, "PartialEq")
+            || self.is_trait_delegated("PartialEq")
+    }
+
+    /// Returns `true` if a trait with the given name is delegated with an `impl Trait` block.
+    pub(crate) fn is_trait_delegated(&self, trait_name: &str) -> bool {
+        self.trait_impls.iter().any(|t| {
+            t.path
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == trait_name)
+        })
+    }
+
+    /// Returns `true` if the enum implements `PartialOrd`, either derived or via `compare` or
+    /// `transparent(Ord)`.
+    pub(crate) fn has_partial_ord(&self) -> bool {
+        self.optional_impl.compare
+            || self.optional_impl.transparent.ord
+            || is_trait_derived(&self.attrs, "PartialOrd")
+    }
+
+    /// Returns `true` if the enum implements `Ord`, either derived or via `transparent(Ord)`.
+    pub(crate) fn has_ord(&self) -> bool {
+        self.optional_impl.transparent.ord || is_trait_derived(&self.attrs, "Ord")
+    }
+
+    /// Generates vector accessor methods for a given `Vec` field in a vec wrapper.
+    pub(crate) fn variant_vec_tokens(&self, vec_field: &Ident) -> TokenStream {
+        let methods = self
+            .variants
+            .iter()
+            .map(|v| v.vec_methods_tokens(&self.ident, vec_field, &self.naming));
+        quote! { #(#methods)* }
+    }
+
+    /// Generates the generic vector accessor methods using the `{Enum}Member` trait.
+    pub(crate) fn member_vec_tokens(&self, vec_field: &Ident) -> TokenStream {
+        if !self.optional_impl.is_as {
+            return TokenStream::new();
+        }
+        let (_, ty_generics, _) = self.generics.split_for_impl();
+        let member = self.member_trait_ident();
+        let new_type = self.generics.new_type();
+        let lt = self.generics.new_lifetime();
+        quote! {
+            /// Returns the first value of type `T`.
+            pub fn first_of<#new_type: #member #ty_generics>(&self) -> ::core::option::Option<&#new_type> {
+                self.#vec_field.iter().find_map(<#new_type as #member #ty_generics>::get)
+            }
+
+            /// Returns the first value of type `T` as mutable reference.
+            pub fn first_of_mut<#new_type: #member #ty_generics>(&mut self) -> ::core::option::Option<&mut #new_type> {
+                self.#vec_field.iter_mut().find_map(<#new_type as #member #ty_generics>::get_mut)
+            }
+
+            /// Returns an iterator over the values of type `T`.
+            pub fn iter_of<#lt, #new_type: #member #ty_generics + #lt>(&#lt self) -> impl ::core::iter::Iterator<Item = &#lt #new_type> {
+                self.#vec_field.iter().filter_map(<#new_type as #member #ty_generics>::get)
+            }
+
+            /// Returns a mutable iterator over the values of type `T`.
+            pub fn iter_of_mut<#lt, #new_type: #member #ty_generics + #lt>(&#lt mut self) -> impl ::core::iter::Iterator<Item = &#lt mut #new_type> {
+                self.#vec_field.iter_mut().filter_map(<#new_type as #member #ty_generics>::get_mut)
+            }
+
+            /// Counts the values of type `T`.
+            pub fn count_of<#new_type: #member #ty_generics>(&self) -> usize {
+                self.iter_of::<#new_type>().count()
+            }
+        }
+    }
+
+    /// returns a `TokenStream` that is always included
+    fn default_tokens(&self) -> TokenStream {
+        let from = self.from_tokens();
+        let variant_eq = if self.has_partial_eq() {
+            self.variant_eq_tokens()
+        } else {
+            Vec::new()
+        };
+        quote! {
+            #(#from)*
+            #(#variant_eq)*
+        }
+    }
+
+    #[allow(clippy::too_many_lines)]
+    fn optional_tokens(&self) -> TokenStream {
+        if self.optional_impl.none() {
+            // depreciated feature flags only if no features are set
+            #[cfg(feature = "try_into")]
+            eprintln!(
+                "Warning: The `try_into` cargo feature is deprecated. Use `impl TryInto` in the nodyn! macro."
+            );
+            #[cfg(feature = "introspection")]
+            eprintln!(
+                "Warning: The `introspection` cargo feature is deprecated. Use `impl introspection` in the nodyn! macro."
+            );
+            #[cfg(feature = "is_as")]
+            eprintln!(
+                "Warning: The `is_as` cargo feature is deprecated. Use `impl is_as` in the nodyn! macro."
+            );
+            #[cfg(feature = "try_into")]
+            let try_into = self.try_from_tokens();
+            #[cfg(not(feature = "try_into"))]
+            let try_into = Vec::<proc_macro2::TokenStream>::new();
+
+            #[cfg(feature = "introspection")]
+            let type_fns = self.introspection_tokens();
+            #[cfg(not(feature = "introspection"))]
+            let type_fns = proc_macro2::TokenStream::new();
+
+            #[cfg(feature = "is_as")]
+            let is_as_fn = self.is_as_tokens().unwrap();
+            #[cfg(not(feature = "is_as"))]
+            let is_as_fn = proc_macro2::TokenStream::new();
+
+            quote! {
+                #(#try_into)*
+                #type_fns
+                #is_as_fn
+            }
+        } else {
+            let try_into = if self.optional_impl.try_into {
+                self.try_from_tokens()
+            } else {
+                Vec::new()
+            };
+            let type_fns = if self.optional_impl.introspection {
+                let any = self.any_tokens();
+                let introspection = self.introspection_tokens();
+                quote! { #any #introspection }
+            } else {
+                proc_macro2::TokenStream::new()
+            };
+            let is_as_fn = if self.optional_impl.is_as {
+                self.is_as_tokens().unwrap()
+            } else {
+                proc_macro2::TokenStream::new()
+            };
+            let compare = if self.optional_impl.compare {
+                self.compare_tokens()
+            } else {
+                proc_macro2::TokenStream::new()
+            };
+            let from_str = if self.optional_impl.from_str {
+                self.from_str_tokens()
+            } else {
+                proc_macro2::TokenStream::new()
+            };
+            let transparent = self.transparent_tokens();
+            let error = self.error_tokens();
+            let as_dyn = self.as_dyn_tokens();
+            let for_each_type = if self.optional_impl.for_each_type {
+                self.for_each_type_tokens()
+            } else {
+                proc_macro2::TokenStream::new()
+            };
+            let match_type = if self.optional_impl.match_type {
+                self.match_type_tokens()
+            } else {
+                proc_macro2::TokenStream::new()
+            };
+            let dispatch = if self.optional_impl.dispatch {
+                self.dispatch_tokens()
+            } else {
+                proc_macro2::TokenStream::new()
+            };
+            let combinators = if self.optional_impl.combinators {
+                self.combinators_tokens()
+            } else {
+                proc_macro2::TokenStream::new()
+            };
+            let serde = self.serde_tokens();
+            let codec = if self.optional_impl.codec {
+                self.codec_tokens()
+            } else {
+                proc_macro2::TokenStream::new()
+            };
+            let oneof = if self.optional_impl.oneof {
+                self.oneof_tokens()
+            } else {
+                proc_macro2::TokenStream::new()
+            };
+            let ffi = if self.optional_impl.ffi {
+                self.ffi_tokens()
+            } else {
+                proc_macro2::TokenStream::new()
+            };
+            quote! {
+                #(#try_into)*
+                #type_fns
+                #is_as_fn
+                #compare
+                #from_str
+                #transparent
+                #error
+                #as_dyn
+                #for_each_type
+                #match_type
+                #dispatch
+                #combinators
+                #serde
+                #codec
+                #oneof
+                #ffi
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_str;
+
+    #[test]
+    fn test_nodyn_enum_parsing() {
+        let input = parse_str::<NodynEnum>(
+            "
+            #[derive(Debug)]
+            pub enum MyEnum {
+                Number(i32),
+                String,
+            }
+            ",
+        )
+        .unwrap();
+
+        assert_eq!(input.ident.to_string(), "MyEnum");
+        assert_eq!(input.variants.len(), 2);
+        assert_eq!(input.variants[0].ident.to_string(), "Number");
+        assert_eq!(input.variants[0].type_to_string(), "i32");
+        assert_eq!(input.variants[1].ident.to_string(), "String");
+        assert_eq!(input.variants[1].type_to_string(), "String");
+        assert_eq!(input.method_impls.len(), 0);
+        assert_eq!(input.trait_impls.len(), 0);
+        assert_eq!(input.vec_wrappers.len(), 0);
+        assert_eq!(input.attrs.len(), 1); // #[derive(Debug)]
+    }
+
+    #[test]
+    fn test_duplicate_variant_types() {
+        let result = parse_str::<NodynEnum>(
+            "
+            pub enum MyEnum {
+                Number(i32),
+                Another(i32),
+            }
+            ",
+        );
+        assert!(result.is_err());
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Duplicate variant type")
+        );
+    }
+
+    #[test]
+    fn test_vec_wrapper_parsing() {
+        let input = parse_str::<NodynEnum>(
+            "
+            pub enum MyEnum {
+                Number(i32),
+            }
+            vec MyEnumVec;
+            ",
+        )
+        .unwrap();
+
+        assert_eq!(input.vec_wrappers.len(), 1);
+        assert_eq!(
+            input.vec_wrappers[0].definition.ident.to_string(),
+            "MyEnumVec"
+        );
+    }
+
+    #[test]
+    fn test_features_parsing() {
+        let input = parse_str::<NodynEnum>(
+            "
+            pub enum MyEnum {
+                Number(i32),
+            }
+            impl TryInto;
+            ",
+        )
+        .unwrap();
+
+        assert!(input.optional_impl.try_into);
+        assert!(!input.optional_impl.is_as);
         assert!(!input.optional_impl.introspection);
+        assert!(!input.optional_impl.compare);
+    }
+
+    #[test]
+    fn test_serde_parsing() {
+        let input = parse_str::<NodynEnum>(
+            "
+            pub enum MyEnum {
+                #[serde(rename = \"number\")]
+                i32,
+                String,
+            }
+            impl serde(adjacent);
+            impl serde::Serialize {
+                fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error>;
+            }
+            ",
+        )
+        .unwrap();
+
+        assert_eq!(input.optional_impl.serde, Some(SerdeRepr::Adjacent));
+        assert_eq!(input.trait_impls.len(), 1);
+        assert_eq!(input.variants[0].serde_name(), "number");
+        assert_eq!(input.variants[1].serde_name(), "String");
+
+        let input = parse_str::<NodynEnum>(
+            "
+            pub enum MyEnum {
+                #[tag = 2]
+                u8,
+            }
+            impl serde;
+            ",
+        )
+        .unwrap();
+
+        assert_eq!(input.optional_impl.serde, Some(SerdeRepr::Tagged));
+        assert_eq!(input.variants[0].serde_name(), "2");
+    }
+
+    #[test]
+    fn test_iterator_preset_parsing() {
+        let input = parse_str::<NodynEnum>(
+            "
+            pub enum Numbers {
+                Range<u32>,
+                Rev<Range<u32>>,
+            }
+            impl Iterator<Item = u32> + DoubleEndedIterator + FusedIterator;
+            ",
+        )
+        .unwrap();
+
+        assert_eq!(input.trait_impls.len(), 3);
+        assert_eq!(input.trait_impls[0].block.functions.len(), 2);
+        assert!(input.trait_impls[2].bound_variants);
+
+        let result = parse_str::<NodynEnum>(
+            "
+            pub enum Numbers {
+                Range<u32>,
+            }
+            impl Iterator<Item = u32> + Clone;
+            ",
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_tag_parsing() {
+        let input = parse_str::<NodynEnum>(
+            "
+            pub enum Value {
+                #[tag = 7]
+                i32,
+                #[tag = 0x10]
+                String,
+            }
+            ",
+        )
+        .unwrap();
+
+        assert!(input.has_tags());
+        assert_eq!(input.variants[0].tag_value(), Some(7));
+        assert_eq!(input.variants[1].tag_value(), Some(16));
+        assert!(input.variants[0].attrs.is_empty());
+
+        let missing = parse_str::<NodynEnum>(
+            "
+            pub enum Value {
+                #[tag = 7]
+                i32,
+                String,
+            }
+            ",
+        );
+        assert!(missing.is_err());
+    }
+
+    #[test]
+    fn test_meta_parsing() {
+        let input = parse_str::<NodynEnum>(
+            "
+            pub enum Value {
+                #[meta(label = \"Integer\")]
+                #[meta(unit = \"count\")]
+                i32,
+                #[meta(unit = \"\", label = \"Text\")]
+                String,
+            }
+            ",
+        )
+        .unwrap();
+
+        assert_eq!(input.meta_keys(), ["label", "unit"]);
+        let unit = format_ident!("unit");
+        assert_eq!(
+            input.variants[0].meta_value(&unit).unwrap().value(),
+            "count"
+        );
+        assert!(input.variants[1].attrs.is_empty());
+
+        for source in [
+            "enum Value { #[meta(label = \"a\")] i32, String }",
+            "enum Value { #[meta(label = \"a\", label = \"b\")] i32 }",
+            "enum Value { #[meta(label = 1)] i32 }",
+            "enum Value { #[meta(a::b = \"a\")] i32 }",
+        ] {
+            assert!(parse_str::<NodynEnum>(source).is_err(), "{source}");
+        }
+    }
+
+    #[test]
+    fn test_naming_parsing() {
+        let input = parse_str::<NodynEnum>(
+            "
+            #[derive(Debug)]
+            #[nodyn(accessor = \"as_{}\", case = \"snake\")]
+            pub enum Value {
+                HTTPResponse,
+                #[nodyn(snake = \"json\")]
+                JSONBody,
+            }
+            ",
+        )
+        .unwrap();
+
+        assert_eq!(input.attrs.len(), 1);
+        assert_eq!(input.naming.is, "is_{}");
+        assert_eq!(input.naming.accessor, "as_{}");
+        assert_eq!(input.variants[0].ident_to_snake(), "h_t_t_p_response");
+        assert_eq!(input.variants[1].ident_to_snake(), "json");
+        assert!(input.variants[1].attrs.is_empty());
+
+        for source in [
+            "#[nodyn(accessor = \"as\")] enum Value { i32 }",
+            "#[nodyn(accessor = \"as {}\")] enum Value { i32 }",
+            "#[nodyn(case = \"kebab\")] enum Value { i32 }",
+            "#[nodyn(prefix = \"as_{}\")] enum Value { i32 }",
+            "enum Value { #[nodyn(snake = \"\")] i32 }",
+            "enum Value { #[nodyn(snake = \"a-b\")] i32 }",
+        ] {
+            assert!(parse_str::<NodynEnum>(source).is_err(), "{source}");
+        }
+    }
+
+    #[test]
+    fn test_error_parsing() {
+        let input = parse_str::<NodynEnum>(
+            "
+            pub enum MyError {
+                #[context(\"parsing\")]
+                ParseIntError,
+            }
+            impl Error;
+            ",
+        )
+        .unwrap();
+
+        assert_eq!(input.optional_impl.error, Some(ErrorSource::Inner));
+        assert!(input.variants[0].context.is_some());
+
+        let input = parse_str::<NodynEnum>(
+            "
+            pub enum MyError {
+                ParseIntError,
+            }
+            impl Error(source);
+            ",
+        )
+        .unwrap();
+
+        assert_eq!(input.optional_impl.error, Some(ErrorSource::Variant));
+
+        let input = parse_str::<NodynEnum>(
+            "
+            pub enum MyError {
+                ParseIntError,
+            }
+            impl Error {
+                fn source(&self) -> Option<&(dyn Error + 'static)>;
+            }
+            ",
+        )
+        .unwrap();
+
+        assert_eq!(input.optional_impl.error, None);
+        assert_eq!(input.trait_impls.len(), 1);
+    }
+
+    #[test]
+    fn test_transparent_parsing() {
+        let input = parse_str::<NodynEnum>(
+            "
+            pub enum MyEnum {
+                Number(i32),
+            }
+            impl transparent(Display);
+            ",
+        )
+        .unwrap();
+
+        assert!(input.optional_impl.transparent.display);
+        assert!(!input.optional_impl.transparent.debug);
+
+        let input = parse_str::<NodynEnum>(
+            "
+            pub enum MyEnum {
+                Name(String),
+            }
+            impl transparent(Hash, Eq, Borrow<str>);
+            ",
+        )
+        .unwrap();
+
+        assert!(input.optional_impl.transparent.hash);
+        assert!(input.optional_impl.transparent.eq);
+        assert!(!input.optional_impl.transparent.ord);
+        assert!(input.optional_impl.transparent.borrow.is_some());
+
+        let result = parse_str::<NodynEnum>(
+            "
+            pub enum MyEnum {
+                Number(i32),
+            }
+            impl transparent(Clone);
+            ",
+        );
+        assert!(result.is_err());
     }
 }
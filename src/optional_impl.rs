@@ -1,17 +1,195 @@
-use syn::{Token, parse::Parse};
+use proc_macro2::Span;
+use syn::{
+    GenericArgument, Ident, Path, PathArguments, Token, Type, parenthesized, parse::Parse,
+    punctuated::Punctuated, token,
+};
 
 use crate::keyword;
 
-// #[allow(clippy::struct_excessive_bools)]
-#[derive(Debug, Copy, Clone, Default)]
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone, Default)]
 pub(crate) struct OptionalImpl {
     pub(crate) try_into: bool,
     pub(crate) is_as: bool,
     pub(crate) introspection: bool,
+    pub(crate) compare: bool,
+    pub(crate) from_str: bool,
+    pub(crate) transparent: Transparent,
+    pub(crate) error: Option<ErrorSource>,
+    /// Traits to bridge to trait objects (via `impl as_dyn`), empty for all delegated traits.
+    pub(crate) as_dyn: Option<Vec<Ident>>,
+    /// Generate the `{enum}_for_each_type!` macro (via `impl for_each_type`).
+    pub(crate) for_each_type: bool,
+    /// Generate the `{enum}_match!` macro (via `impl match_type`).
+    pub(crate) match_type: bool,
+    /// Generate the `{enum}_dispatch!` macro (via `impl dispatch`).
+    pub(crate) dispatch: bool,
+    /// Generate the Option-like variant methods (via `impl combinators`).
+    pub(crate) combinators: bool,
+    /// Representation of the `Serialize` and `Deserialize` impls (via `impl serde`).
+    pub(crate) serde: Option<SerdeRepr>,
+    /// Implement `NodynEncode` (via `impl codec`).
+    pub(crate) codec: bool,
+    /// Generate the `{enum}OneOf` struct (via `impl oneof`).
+    pub(crate) oneof: bool,
+    /// Generate the `#[repr(C)]` `{enum}Ffi` struct (via `impl ffi`).
+    pub(crate) ffi: bool,
+    /// Where the features implementing the same traits were requested.
+    spans: FeatureSpans,
+}
+
+/// Spans of the features which implement the same std traits, to reject them together.
+#[derive(Debug, Copy, Clone, Default)]
+struct FeatureSpans {
+    compare: Option<Span>,
+    error: Option<Span>,
+    display: Option<Span>,
+    eq: Option<Span>,
+    ord: Option<Span>,
+}
+
+impl FeatureSpans {
+    fn merge(&mut self, other: Self) {
+        self.compare = other.compare.or(self.compare);
+        self.error = other.error.or(self.error);
+        self.display = other.display.or(self.display);
+        self.eq = other.eq.or(self.eq);
+        self.ord = other.ord.or(self.ord);
+    }
+}
+
+/// How `impl serde` represents the variants.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum SerdeRepr {
+    /// `{"name": value}` (`impl serde` or `impl serde(tagged)`).
+    Tagged,
+    /// Only the value, deserialized as the first variant that succeeds (`impl serde(untagged)`).
+    Untagged,
+    /// `{"tag": "name", "content": value}` (`impl serde(adjacent)`).
+    Adjacent,
+}
+
+impl Parse for SerdeRepr {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        if !input.peek(token::Paren) {
+            return Ok(Self::Tagged);
+        }
+        let content;
+        parenthesized!(content in input);
+        let ident = content.parse::<Ident>()?;
+        match ident.to_string().as_str() {
+            "tagged" if content.is_empty() => Ok(Self::Tagged),
+            "untagged" if content.is_empty() => Ok(Self::Untagged),
+            "adjacent" if content.is_empty() => Ok(Self::Adjacent),
+            _ => Err(syn::Error::new(
+                ident.span(),
+                "Expected `tagged`, `untagged` or `adjacent`",
+            )),
+        }
+    }
+}
+
+/// What `Error::source` returns for an error enum (via `impl Error`).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum ErrorSource {
+    /// The source of the wrapped error (`impl Error`).
+    Inner,
+    /// The wrapped error itself (`impl Error(source)`).
+    Variant,
+}
+
+impl Parse for ErrorSource {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        if !input.peek(token::Paren) {
+            return Ok(Self::Inner);
+        }
+        let content;
+        parenthesized!(content in input);
+        let ident = content.parse::<Ident>()?;
+        if ident == "source" && content.is_empty() {
+            Ok(Self::Variant)
+        } else {
+            Err(syn::Error::new(ident.span(), "Expected `source`"))
+        }
+    }
+}
+
+/// Traits implemented by forwarding directly to the wrapped value (via `impl transparent(..)`).
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Transparent {
+    pub(crate) debug: bool,
+    pub(crate) display: bool,
+    pub(crate) hash: bool,
+    pub(crate) eq: bool,
+    pub(crate) ord: bool,
+    /// The `T` of `Borrow<T>`, every variant type must implement `Borrow<T>`.
+    pub(crate) borrow: Option<Type>,
+    spans: FeatureSpans,
+}
+
+impl Transparent {
+    fn merge(&mut self, other: Self) {
+        self.debug |= other.debug;
+        self.display |= other.display;
+        self.hash |= other.hash;
+        self.eq |= other.eq;
+        self.ord |= other.ord;
+        if other.borrow.is_some() {
+            self.borrow = other.borrow;
+        }
+    }
+
+    const fn none(&self) -> bool {
+        !self.debug && !self.display && !self.hash && !self.eq && !self.ord && self.borrow.is_none()
+    }
+}
+
+impl Parse for Transparent {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut transparent = Self::default();
+        let content;
+        parenthesized!(content in input);
+        for path in Punctuated::<Path, Token![,]>::parse_terminated(&content)? {
+            let Some(segment) = path.segments.last() else {
+                continue;
+            };
+            match (segment.ident.to_string().as_str(), &segment.arguments) {
+                ("Debug", PathArguments::None) => transparent.debug = true,
+                ("Display", PathArguments::None) => {
+                    transparent.display = true;
+                    transparent.spans.display = Some(segment.ident.span());
+                }
+                ("Hash", PathArguments::None) => transparent.hash = true,
+                ("Eq", PathArguments::None) => {
+                    transparent.eq = true;
+                    transparent.spans.eq = Some(segment.ident.span());
+                }
+                ("Ord", PathArguments::None) => {
+                    transparent.ord = true;
+                    transparent.spans.ord = Some(segment.ident.span());
+                }
+                ("Borrow", PathArguments::AngleBracketed(args)) if args.args.len() == 1 => {
+                    if let Some(GenericArgument::Type(ty)) = args.args.first() {
+                        transparent.borrow = Some(ty.clone());
+                    }
+                }
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        path,
+                        "Unsupported transparent trait, expected `Debug`, `Display`, `Hash`, `Eq`, `Ord` or `Borrow<T>`",
+                    ));
+                }
+            }
+        }
+        Ok(transparent)
+    }
 }
 
 impl OptionalImpl {
-    pub(crate) const fn merge(&mut self, other: Self) {
+    /// Merges the features of another `impl` line, rejecting features which implement the
+    /// same traits.
+    pub(crate) fn merge(&mut self, other: Self) -> syn::Result<()> {
         if other.try_into {
             self.try_into = true;
         }
@@ -21,10 +199,95 @@ impl OptionalImpl {
         if other.introspection {
             self.introspection = true;
         }
+        if other.compare {
+            self.compare = true;
+        }
+        if other.from_str {
+            self.from_str = true;
+        }
+        self.transparent.merge(other.transparent);
+        if other.error.is_some() {
+            self.error = other.error;
+        }
+        if let Some(traits) = other.as_dyn {
+            self.as_dyn.get_or_insert_default().extend(traits);
+        }
+        if other.for_each_type {
+            self.for_each_type = true;
+        }
+        if other.match_type {
+            self.match_type = true;
+        }
+        if other.dispatch {
+            self.dispatch = true;
+        }
+        if other.combinators {
+            self.combinators = true;
+        }
+        if other.serde.is_some() {
+            self.serde = other.serde;
+        }
+        if other.codec {
+            self.codec = true;
+        }
+        if other.oneof {
+            self.oneof = true;
+        }
+        if other.ffi {
+            self.ffi = true;
+        }
+        self.spans.merge(other.spans);
+        self.check_conflicts()
+    }
+
+    /// Checks that no two features implement the same trait.
+    fn check_conflicts(&self) -> syn::Result<()> {
+        let spans = &self.spans;
+        let mut errors = Vec::new();
+        if let (Some(_), Some(display)) = (spans.error, spans.display) {
+            errors.push(syn::Error::new(
+                display,
+                "`transparent(Display)` conflicts with `impl Error`, which already implements `Display`",
+            ));
+        }
+        if let (Some(_), Some(eq)) = (spans.compare, spans.eq) {
+            errors.push(syn::Error::new(
+                eq,
+                "`transparent(Eq)` conflicts with `impl compare`, which already implements `PartialEq`",
+            ));
+        }
+        if let (Some(_), Some(ord)) = (spans.compare, spans.ord) {
+            errors.push(syn::Error::new(
+                ord,
+                "`transparent(Ord)` conflicts with `impl compare`, which already implements `PartialOrd`",
+            ));
+        }
+        match errors.into_iter().reduce(|mut all, error| {
+            all.combine(error);
+            all
+        }) {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
     }
 
-    pub(crate) const fn none(self) -> bool {
-        !self.try_into && !self.is_as && !self.introspection
+    pub(crate) const fn none(&self) -> bool {
+        !self.try_into
+            && !self.is_as
+            && !self.introspection
+            && !self.compare
+            && !self.from_str
+            && self.transparent.none()
+            && self.error.is_none()
+            && self.as_dyn.is_none()
+            && !self.for_each_type
+            && !self.match_type
+            && !self.dispatch
+            && !self.combinators
+            && self.serde.is_none()
+            && !self.codec
+            && !self.oneof
+            && !self.ffi
     }
 }
 
@@ -41,6 +304,58 @@ impl Parse for OptionalImpl {
             } else if input.peek(keyword::introspection) {
                 let _ = input.parse::<keyword::introspection>()?;
                 optional.introspection = true;
+            } else if input.peek(keyword::compare) {
+                let compare = input.parse::<keyword::compare>()?;
+                optional.compare = true;
+                optional.spans.compare = Some(compare.span);
+            } else if input.peek(keyword::FromStr) {
+                let _ = input.parse::<keyword::FromStr>()?;
+                optional.from_str = true;
+            } else if input.peek(keyword::transparent) {
+                let _ = input.parse::<keyword::transparent>()?;
+                let transparent = input.parse::<Transparent>()?;
+                optional.spans.merge(transparent.spans);
+                optional.transparent.merge(transparent);
+            } else if input.peek(keyword::as_dyn) {
+                let _ = input.parse::<keyword::as_dyn>()?;
+                let traits = if input.peek(token::Paren) {
+                    let content;
+                    parenthesized!(content in input);
+                    Punctuated::<Ident, Token![,]>::parse_terminated(&content)?
+                        .into_iter()
+                        .collect()
+                } else {
+                    Vec::new()
+                };
+                optional.as_dyn = Some(traits);
+            } else if input.peek(keyword::for_each_type) {
+                let _ = input.parse::<keyword::for_each_type>()?;
+                optional.for_each_type = true;
+            } else if input.peek(keyword::match_type) {
+                let _ = input.parse::<keyword::match_type>()?;
+                optional.match_type = true;
+            } else if input.peek(keyword::dispatch) {
+                let _ = input.parse::<keyword::dispatch>()?;
+                optional.dispatch = true;
+            } else if input.peek(keyword::combinators) {
+                let _ = input.parse::<keyword::combinators>()?;
+                optional.combinators = true;
+            } else if input.peek(keyword::serde) && !input.peek2(Token![::]) {
+                let _ = input.parse::<keyword::serde>()?;
+                optional.serde = Some(input.parse::<SerdeRepr>()?);
+            } else if input.peek(keyword::codec) {
+                let _ = input.parse::<keyword::codec>()?;
+                optional.codec = true;
+            } else if input.peek(keyword::oneof) {
+                let _ = input.parse::<keyword::oneof>()?;
+                optional.oneof = true;
+            } else if input.peek(keyword::ffi) {
+                let _ = input.parse::<keyword::ffi>()?;
+                optional.ffi = true;
+            } else if input.peek(keyword::Error) && !input.peek2(token::Brace) {
+                let error = input.parse::<keyword::Error>()?;
+                optional.error = Some(input.parse::<ErrorSource>()?);
+                optional.spans.error = Some(error.span);
             } else {
                 break;
             }
@@ -48,6 +363,7 @@ impl Parse for OptionalImpl {
         if input.peek(Token![;]) {
             let _ = input.parse::<syn::token::Semi>()?;
         }
+        optional.check_conflicts()?;
         Ok(optional)
     }
 }
@@ -0,0 +1,178 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{
+    Block, Expr, ExprClosure, Ident, Item, ItemFn, ReturnType, Stmt, StmtMacro, Type,
+    TypeImplTrait, TypeParamBound, parse::Parse, parse_quote, visit_mut::VisitMut,
+};
+
+use crate::{NodynEnum, TraitImpl};
+
+/// Arguments of `#[nodyn::returns(impl Trait)]`, when empty the
+/// `impl Trait` return type of the function is used.
+pub(crate) struct Returns {
+    impl_trait: Option<TypeImplTrait>,
+}
+
+impl Parse for Returns {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        if input.is_empty() {
+            Ok(Self { impl_trait: None })
+        } else {
+            Ok(Self {
+                impl_trait: Some(input.parse::<TypeImplTrait>()?),
+            })
+        }
+    }
+}
+
+impl Returns {
+    /// Wraps each `marker!(expr)` in the function into a variant of an anonymous
+    /// enum, defined in the function body, which implements the traits.
+    ///
+    /// The enum is generic over the variant types so these are inferred by the
+    /// compiler: `enum __NodynReturns<T0, T1> { V0(T0), V1(T1) }`.
+    pub(crate) fn expand(self, mut function: ItemFn) -> syn::Result<TokenStream> {
+        let impl_trait = match (self.impl_trait, &function.sig.output) {
+            (Some(impl_trait), _) => impl_trait,
+            (None, ReturnType::Type(_, ty)) => match ty.as_ref() {
+                Type::ImplTrait(impl_trait) => impl_trait.clone(),
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        ty,
+                        "Expected an `impl Trait` return type or `#[returns(impl Trait)]`",
+                    ));
+                }
+            },
+            (None, ReturnType::Default) => {
+                return Err(syn::Error::new_spanned(
+                    &function.sig,
+                    "Expected an `impl Trait` return type or `#[returns(impl Trait)]`",
+                ));
+            }
+        };
+
+        let enum_ident = Ident::new("__NodynReturns", Span::mixed_site());
+        let mut markers = Markers {
+            enum_ident: &enum_ident,
+            count: 0,
+            errors: Vec::new(),
+        };
+        markers.visit_block_mut(&mut function.block);
+        if let Some(error) = markers.errors.into_iter().reduce(|mut all, error| {
+            all.combine(error);
+            all
+        }) {
+            return Err(error);
+        }
+        if markers.count == 0 {
+            return Err(syn::Error::new_spanned(
+                &function.sig.ident,
+                "No `marker!(..)` found to wrap the returned values",
+            ));
+        }
+
+        // lifetime bounds are left out, the type parameters capture them
+        let bounds = impl_trait
+            .bounds
+            .iter()
+            .filter_map(|bound| match bound {
+                TypeParamBound::Trait(bound) => Some(bound),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let trait_impls = bounds
+            .iter()
+            .map(|bound| TraitImpl::preset(&bound.path))
+            .collect::<syn::Result<Vec<_>>>()?;
+
+        let params = (0..markers.count)
+            .map(|i| format_ident!("T{}", i))
+            .collect::<Vec<_>>();
+        let variants = params
+            .iter()
+            .enumerate()
+            .map(|(i, param)| {
+                let ident = format_ident!("V{}", i);
+                quote! { #ident(#param) }
+            })
+            .collect::<Vec<_>>();
+        let mut nodyn_enum: NodynEnum = parse_quote! {
+            enum #enum_ident<#(#params,)*> {
+                #(#variants,)*
+            }
+        };
+        // unpinning the enum requires the variants to be `Unpin`
+        let unpin = trait_impls
+            .iter()
+            .any(|t| t.block.has_pinned_receiver())
+            .then(|| quote! { + ::core::marker::Unpin });
+        let bounds = quote! { #(#bounds)+* #unpin };
+        nodyn_enum.generics.where_clause = Some(parse_quote! {
+            where #(#params: #bounds,)*
+        });
+        nodyn_enum.trait_impls = trait_impls;
+
+        let enum_definition = nodyn_enum.enum_definition_tokens();
+        let traits = nodyn_enum.trait_tokens();
+        let stmts = &function.block.stmts;
+        let block: Block = parse_quote! {{
+            #enum_definition
+            #(#traits)*
+            #(#stmts)*
+        }};
+        *function.block = block;
+        Ok(quote! { #function })
+    }
+}
+
+/// Replaces `marker!(expr)` with `__NodynReturns::V{n}(expr)`, numbering the markers.
+///
+/// Closures and nested items are skipped, as they don't return from the function.
+struct Markers<'a> {
+    enum_ident: &'a Ident,
+    count: usize,
+    errors: Vec<syn::Error>,
+}
+
+impl Markers<'_> {
+    fn wrap(&mut self, mac: &syn::Macro) -> Expr {
+        match mac.parse_body::<Expr>() {
+            Ok(expr) => {
+                let enum_ident = self.enum_ident;
+                let variant = format_ident!("V{}", self.count);
+                self.count += 1;
+                parse_quote! { #enum_ident::#variant(#expr) }
+            }
+            Err(error) => {
+                self.errors.push(error);
+                parse_quote! { () }
+            }
+        }
+    }
+}
+
+impl VisitMut for Markers<'_> {
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        match expr {
+            Expr::Macro(expr_macro) if expr_macro.mac.path.is_ident("marker") => {
+                *expr = self.wrap(&expr_macro.mac);
+            }
+            _ => syn::visit_mut::visit_expr_mut(self, expr),
+        }
+    }
+
+    fn visit_stmt_mut(&mut self, stmt: &mut Stmt) {
+        match stmt {
+            Stmt::Macro(StmtMacro {
+                mac, semi_token, ..
+            }) if mac.path.is_ident("marker") => {
+                *stmt = Stmt::Expr(self.wrap(mac), *semi_token);
+            }
+            _ => syn::visit_mut::visit_stmt_mut(self, stmt),
+        }
+    }
+
+    fn visit_expr_closure_mut(&mut self, _closure: &mut ExprClosure) {}
+
+    fn visit_item_mut(&mut self, _item: &mut Item) {}
+}
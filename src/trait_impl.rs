@@ -1,4 +1,7 @@
-use syn::{Path, parse::Parse};
+use syn::{
+    AssocType, FnArg, GenericArgument, Ident, ImplItem, Path, PathArguments, ReturnType, Signature,
+    Token, Type, TypeParamBound, WherePredicate, parse::Parse, parse_quote, token,
+};
 
 use crate::MethodImpl;
 
@@ -7,6 +10,12 @@ use crate::MethodImpl;
 pub(crate) struct TraitImpl {
     pub(crate) path: Path,
     pub(crate) block: MethodImpl,
+    /// Every variant type must implement the trait, checked with a `where` clause.
+    /// Used for marker traits which have no methods to delegate.
+    pub(crate) bound_variants: bool,
+    /// Call the methods with fully qualified syntax, as done for presets, whose
+    /// trait may not be in scope and whose methods may clash, like `fmt`.
+    pub(crate) qualified: bool,
 }
 
 impl Parse for TraitImpl {
@@ -14,6 +23,209 @@ impl Parse for TraitImpl {
         Ok(Self {
             path: input.parse::<Path>()?,
             block: input.parse::<MethodImpl>()?,
+            bound_variants: false,
+            qualified: false,
         })
     }
 }
+
+impl TraitImpl {
+    /// Parses a trait block, or a preset for a trait with associated types.
+    ///
+    /// The presets accepted here are `Iterator<Item = T>` and `Future<Output = T>`,
+    /// optionally followed by presets like `+ DoubleEndedIterator`, see [`TraitImpl::preset`].
+    pub(crate) fn parse_all(input: syn::parse::ParseStream) -> syn::Result<Vec<Self>> {
+        let fork = input.fork();
+        let path = fork.parse::<Path>()?;
+        if fork.peek(token::Brace)
+            || path
+                .segments
+                .last()
+                .is_none_or(|segment| segment.ident != "Iterator" && segment.ident != "Future")
+        {
+            return Ok(vec![input.parse::<Self>()?]);
+        }
+        let _ = input.parse::<Path>()?;
+        let mut presets = vec![Self::preset(&path)?];
+        while input.peek(Token![+]) {
+            let _ = input.parse::<Token![+]>()?;
+            presets.push(Self::preset(&input.parse::<Path>()?)?);
+        }
+        if input.peek(Token![;]) {
+            let _ = input.parse::<Token![;]>()?;
+        }
+        Ok(presets)
+    }
+
+    /// Returns the delegation of a trait the macro knows the methods of.
+    ///
+    /// Supported are `Iterator<Item = T>`, `DoubleEndedIterator`, `ExactSizeIterator`,
+    /// `FusedIterator`, `Future<Output = T>`, `Debug` and `Display`.
+    pub(crate) fn preset(path: &Path) -> syn::Result<Self> {
+        let error = || {
+            syn::Error::new_spanned(
+                path,
+                "Unsupported trait, expected `Iterator<Item = T>`, `DoubleEndedIterator`, \
+                 `ExactSizeIterator`, `FusedIterator`, `Future<Output = T>`, `Debug` or `Display`",
+            )
+        };
+        let segment = path.segments.last().ok_or_else(error)?;
+        let preset = match segment.ident.to_string().as_str() {
+            "Iterator" => {
+                let item = Self::associated_type(path, "Item").ok_or_else(error)?;
+                Self::new(
+                    parse_quote! { ::core::iter::Iterator },
+                    parse_quote! {{
+                        type Item = #item;
+                        fn next(&mut self) -> ::core::option::Option<Self::Item>;
+                        fn size_hint(&self) -> (usize, ::core::option::Option<usize>);
+                    }},
+                )
+            }
+            "DoubleEndedIterator" => Self::new(
+                parse_quote! { ::core::iter::DoubleEndedIterator },
+                parse_quote! {{
+                    fn next_back(&mut self) -> ::core::option::Option<Self::Item>;
+                }},
+            ),
+            "ExactSizeIterator" => Self::new(
+                parse_quote! { ::core::iter::ExactSizeIterator },
+                parse_quote! {{
+                    fn len(&self) -> usize;
+                }},
+            ),
+            "FusedIterator" => Self {
+                bound_variants: true,
+                ..Self::new(
+                    parse_quote! { ::core::iter::FusedIterator },
+                    parse_quote! {{}},
+                )
+            },
+            "Future" => {
+                let output = Self::associated_type(path, "Output").ok_or_else(error)?;
+                Self::new(
+                    parse_quote! { ::core::future::Future },
+                    parse_quote! {{
+                        type Output = #output;
+                        fn poll(
+                            self: ::core::pin::Pin<&mut Self>,
+                            cx: &mut ::core::task::Context<'_>,
+                        ) -> ::core::task::Poll<Self::Output>;
+                    }},
+                )
+            }
+            "Debug" => Self::new(
+                parse_quote! { ::core::fmt::Debug },
+                parse_quote! {{
+                    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result;
+                }},
+            ),
+            "Display" => Self::new(
+                parse_quote! { ::core::fmt::Display },
+                parse_quote! {{
+                    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result;
+                }},
+            ),
+            _ => return Err(error()),
+        };
+        Ok(preset)
+    }
+
+    const fn new(path: Path, block: MethodImpl) -> Self {
+        Self {
+            path,
+            block,
+            bound_variants: false,
+            qualified: true,
+        }
+    }
+
+    /// Returns the `T` of `Trait<name = T>`, like `Iterator<Item = T>`.
+    fn associated_type(path: &Path, name: &str) -> Option<Type> {
+        let PathArguments::AngleBracketed(args) = &path.segments.last()?.arguments else {
+            return None;
+        };
+        match args.args.first() {
+            Some(GenericArgument::AssocType(AssocType { ident, ty, .. }))
+                if ident == name && args.args.len() == 1 =>
+            {
+                Some(ty.clone())
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the name of the trait, the last segment of its path.
+    pub(crate) fn trait_ident(&self) -> Option<&Ident> {
+        self.path.segments.last().map(|segment| &segment.ident)
+    }
+
+    /// Returns why the trait can't be made into a trait object, judging by the
+    /// declared items, or `None` when it can.
+    ///
+    /// Only the items of the trait block are seen here, the compiler checks the others.
+    pub(crate) fn dyn_incompatibility(&self) -> Option<String> {
+        if self
+            .block
+            .items
+            .iter()
+            .any(|item| matches!(item, ImplItem::Const(_)))
+        {
+            return Some("it has an associated constant".to_string());
+        }
+        self.block
+            .functions
+            .iter()
+            .filter(|f| !requires_sized(&f.sig))
+            .find_map(|f| {
+                let name = &f.sig.ident;
+                if !matches!(f.sig.inputs.first(), Some(FnArg::Receiver(_))) {
+                    Some(format!("method `{name}` has no `self` receiver"))
+                } else if f.sig.generics.type_params().next().is_some() {
+                    Some(format!("method `{name}` has generic type parameters"))
+                } else if f.sig.asyncness.is_some() {
+                    Some(format!("method `{name}` is `async`"))
+                } else {
+                    match &f.sig.output {
+                        ReturnType::Type(_, ty)
+                            if matches!(ty.as_ref(), Type::Path(path) if path.path.is_ident("Self")) =>
+                        {
+                            Some(format!("method `{name}` returns `Self`"))
+                        }
+                        ReturnType::Type(_, ty) if matches!(ty.as_ref(), Type::ImplTrait(_)) => {
+                            Some(format!("method `{name}` returns `impl Trait`"))
+                        }
+                        _ => None,
+                    }
+                }
+            })
+    }
+
+    /// Returns the associated types declared in the trait block, as `Name = Type` arguments.
+    pub(crate) fn associated_types(&self) -> Vec<GenericArgument> {
+        self.block
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                ImplItem::Type(assoc) => {
+                    let ident = &assoc.ident;
+                    let ty = &assoc.ty;
+                    Some(parse_quote! { #ident = #ty })
+                }
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Returns `true` if a method has a `where Self: Sized` clause, excluding it from trait objects.
+fn requires_sized(sig: &Signature) -> bool {
+    sig.generics.where_clause.as_ref().is_some_and(|clause| {
+        clause.predicates.iter().any(|predicate| {
+            matches!(predicate, WherePredicate::Type(predicate)
+                if matches!(&predicate.bounded_ty, Type::Path(path) if path.path.is_ident("Self"))
+                && predicate.bounds.iter().any(|bound| matches!(bound,
+                    TypeParamBound::Trait(bound) if bound.path.is_ident("Sized"))))
+        })
+    })
+}
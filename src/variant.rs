@@ -1,14 +1,18 @@
-use proc_macro2::TokenStream;
+use proc_macro2::{TokenStream, TokenTree};
 use quote::{ToTokens, quote};
 use syn::{
-    Attribute, FnArg, GenericArgument, Ident, Path, PathArguments, Token, Type, TypeArray,
-    TypePath, TypeReference, TypeTuple, parenthesized,
+    Attribute, Expr, ExprLit, FnArg, GenericArgument, Generics, Ident, Lit, LitInt, LitStr, Meta,
+    MetaNameValue, Path, PathArguments, Token, Type, TypeArray, TypePath, TypeReference, TypeTuple,
+    Visibility, parenthesized,
     parse::Parse,
     punctuated::Punctuated,
     spanned::Spanned,
     token::{Comma, Paren},
+    visit_mut::VisitMut,
 };
 
+use crate::naming::{Naming, fill, is_ident};
+
 /// Represents a variant in an enum with its attributes, type, and conversion types.
 ///
 /// A `Variant` encapsulates the metadata for a single enum variant, including its identifier,
@@ -23,6 +27,21 @@ pub(crate) struct Variant {
     pub(crate) ident: Ident,
     /// The type held by the variant (e.g., `String`, `i32`).
     pub(crate) ty: Type,
+    /// Whether `FromStr` skips this variant (via `#[parse(skip)]`).
+    pub(crate) parse_skip: bool,
+    /// Order in which `FromStr` tries this variant, highest first (via `#[parse(priority = n)]`).
+    pub(crate) parse_priority: i32,
+    /// Format string used by a transparent `Display` (via `#[display("...")]`).
+    pub(crate) display: Option<LitStr>,
+    /// Prefix for the message of an error enum (via `#[context("...")]`).
+    pub(crate) context: Option<LitStr>,
+    /// Stable identity of the variant, independent of its position (via `#[tag = n]`).
+    pub(crate) tag: Option<LitInt>,
+    /// Constant metadata of the variant as key and value (via `#[meta(key = "value")]`).
+    pub(crate) meta: Vec<(Ident, LitStr)>,
+    /// The `snake_case` name used in generated identifiers, set with
+    /// `#[nodyn(snake = "..")]` or by the `case` of the enum.
+    pub(crate) snake: Option<String>,
 }
 
 impl Variant {
@@ -57,6 +76,201 @@ impl Variant {
         }
     }
 
+    /// Generates a match arm comparing this variant (left) with `other` (right).
+    ///
+    /// Equal variants are compared directly. Different variants are compared
+    /// after promoting one side via `#[into]`, preferring the left side. If
+    /// neither can be promoted the arm returns `None`.
+    pub(crate) fn partial_cmp_arm_tokens(&self, other: &Self, wrapper: &Ident) -> TokenStream {
+        let left = &self.ident;
+        let right = &other.ident;
+        if self.ident == other.ident {
+            let ty = &self.ty;
+            quote! {
+                (#wrapper::#left(l), #wrapper::#right(r)) => <#ty as ::core::cmp::PartialOrd>::partial_cmp(l, r),
+            }
+        } else if self.into.contains(&other.ty) {
+            let ty = &other.ty;
+            quote! {
+                (#wrapper::#left(l), #wrapper::#right(r)) => <#ty as ::core::cmp::PartialOrd>::partial_cmp(
+                    &::core::convert::Into::<#ty>::into(::core::clone::Clone::clone(l)),
+                    r,
+                ),
+            }
+        } else if other.into.contains(&self.ty) {
+            let ty = &self.ty;
+            quote! {
+                (#wrapper::#left(l), #wrapper::#right(r)) => <#ty as ::core::cmp::PartialOrd>::partial_cmp(
+                    l,
+                    &::core::convert::Into::<#ty>::into(::core::clone::Clone::clone(r)),
+                ),
+            }
+        } else {
+            quote! { (#wrapper::#left(_), #wrapper::#right(_)) => ::core::option::Option::None, }
+        }
+    }
+
+    /// Generates a match arm testing this variant (left) and `other` (right) for equality.
+    ///
+    /// Uses the same promotion rules as [`Variant::partial_cmp_arm_tokens`],
+    /// variants that cannot be compared are never equal.
+    pub(crate) fn eq_arm_tokens(&self, other: &Self, wrapper: &Ident) -> TokenStream {
+        let left = &self.ident;
+        let right = &other.ident;
+        if self.ident == other.ident {
+            let ty = &self.ty;
+            quote! {
+                (#wrapper::#left(l), #wrapper::#right(r)) => <#ty as ::core::cmp::PartialEq>::eq(l, r),
+            }
+        } else if self.into.contains(&other.ty) {
+            let ty = &other.ty;
+            quote! {
+                (#wrapper::#left(l), #wrapper::#right(r)) => <#ty as ::core::cmp::PartialEq>::eq(
+                    &::core::convert::Into::<#ty>::into(::core::clone::Clone::clone(l)),
+                    r,
+                ),
+            }
+        } else if other.into.contains(&self.ty) {
+            let ty = &self.ty;
+            quote! {
+                (#wrapper::#left(l), #wrapper::#right(r)) => <#ty as ::core::cmp::PartialEq>::eq(
+                    l,
+                    &::core::convert::Into::<#ty>::into(::core::clone::Clone::clone(r)),
+                ),
+            }
+        } else {
+            quote! { (#wrapper::#left(_), #wrapper::#right(_)) => false, }
+        }
+    }
+
+    /// Generates an attempt to parse `s` as this variant for `FromStr`.
+    ///
+    /// Returns early on success, otherwise records the type name and error message in `errors`.
+    #[allow(clippy::wrong_self_convention)]
+    pub(crate) fn from_str_attempt_tokens(&self, wrapper: &Ident) -> TokenStream {
+        let ident = &self.ident;
+        let ty = &self.ty;
+        let type_name = self.type_to_string();
+        quote! {
+            match <#ty as ::core::str::FromStr>::from_str(s) {
+                ::core::result::Result::Ok(value) => return ::core::result::Result::Ok(#wrapper::#ident(value)),
+                ::core::result::Result::Err(e) => errors.push((#type_name, ::std::string::ToString::to_string(&e))),
+            }
+        }
+    }
+
+    /// Generates a match arm formatting the variant's value with the given `fmt` trait.
+    ///
+    /// If a format string is given, the value is written with it instead.
+    pub(crate) fn fmt_arm_tokens(
+        &self,
+        wrapper: &Ident,
+        fmt_trait: &Path,
+        format: Option<&LitStr>,
+    ) -> TokenStream {
+        let ident = &self.ident;
+        let ty = &self.ty;
+        if let Some(format) = format {
+            quote! { #wrapper::#ident(value) => ::core::write!(f, #format, value = value), }
+        } else {
+            quote! { #wrapper::#ident(value) => <#ty as #fmt_trait>::fmt(value, f), }
+        }
+    }
+
+    /// Generates a match arm hashing the variant's value, without the discriminant.
+    pub(crate) fn hash_arm_tokens(&self, wrapper: &Ident) -> TokenStream {
+        let ident = &self.ident;
+        let ty = &self.ty;
+        quote! { #wrapper::#ident(value) => <#ty as ::core::hash::Hash>::hash(value, state), }
+    }
+
+    /// Generates a match arm borrowing the variant's value as `target`.
+    pub(crate) fn borrow_arm_tokens(&self, wrapper: &Ident, target: &Type) -> TokenStream {
+        let ident = &self.ident;
+        let ty = &self.ty;
+        quote! { #wrapper::#ident(value) => <#ty as ::core::borrow::Borrow<#target>>::borrow(value), }
+    }
+
+    /// Generates a match arm for two values of this variant calling `method` of `cmp_trait`,
+    /// used for the transparent `PartialEq` and `Ord`.
+    pub(crate) fn transparent_cmp_arm_tokens(
+        &self,
+        wrapper: &Ident,
+        cmp_trait: &Path,
+        method: &Ident,
+    ) -> TokenStream {
+        let ident = &self.ident;
+        let ty = &self.ty;
+        quote! { (#wrapper::#ident(l), #wrapper::#ident(r)) => <#ty as #cmp_trait>::#method(l, r), }
+    }
+
+    /// Generates a match arm returning `index`, the position of this variant.
+    pub(crate) fn index_arm_tokens(&self, wrapper: &Ident, index: usize) -> TokenStream {
+        let ident = &self.ident;
+        quote! { #wrapper::#ident(_) => #index, }
+    }
+
+    /// Generates a match arm writing the error message of the variant's value,
+    /// prefixed by its `#[context("...")]`.
+    pub(crate) fn error_display_arm_tokens(&self, wrapper: &Ident) -> TokenStream {
+        let ident = &self.ident;
+        let ty = &self.ty;
+        let context = self.context.as_ref().map(|context| {
+            quote! {
+                f.write_str(#context)?;
+                f.write_str(": ")?;
+            }
+        });
+        quote! {
+            #wrapper::#ident(value) => {
+                #context
+                <#ty as ::core::fmt::Display>::fmt(value, f)
+            }
+        }
+    }
+
+    /// Generates a match arm returning the error source, either the variant's
+    /// value itself or the source of the value.
+    pub(crate) fn error_source_arm_tokens(
+        &self,
+        wrapper: &Ident,
+        value_is_source: bool,
+    ) -> TokenStream {
+        let ident = &self.ident;
+        let ty = &self.ty;
+        if value_is_source {
+            quote! { #wrapper::#ident(value) => ::core::option::Option::Some(value), }
+        } else {
+            quote! { #wrapper::#ident(value) => <#ty as ::core::error::Error>::source(value), }
+        }
+    }
+
+    /// Generates a match arm returning the variant's value as a trait object,
+    /// boxed with `boxed`. The coercion happens at the function's return type.
+    pub(crate) fn dyn_arm_tokens(&self, wrapper: &Ident, boxed: bool) -> TokenStream {
+        let ident = &self.ident;
+        if boxed {
+            quote! { #wrapper::#ident(value) => ::std::boxed::Box::new(value), }
+        } else {
+            quote! { #wrapper::#ident(value) => value, }
+        }
+    }
+
+    /// Generates a downcast of the `Box<dyn Any>` named `value` to this variant's type
+    /// for `from_any`, returning early on success.
+    pub(crate) fn downcast_tokens(&self, wrapper: &Ident) -> TokenStream {
+        let ident = &self.ident;
+        let ty = &self.ty;
+        quote! {
+            let value = match value.downcast::<#ty>() {
+                ::core::result::Result::Ok(value) => {
+                    return ::core::result::Result::Ok(#wrapper::#ident(*value));
+                }
+                ::core::result::Result::Err(value) => value,
+            };
+        }
+    }
+
     /// Generates a match arm for calling a function on the variant's value.
     #[allow(clippy::match_wildcard_for_single_variants)]
     pub(crate) fn fn_call_arm_tokens(
@@ -64,8 +278,50 @@ impl Variant {
         wrapper: &Ident,
         function: &Ident,
         inputs: &Punctuated<FnArg, Comma>,
+    ) -> TokenStream {
+        let args = inputs
+            .iter()
+            .filter_map(|arg| match arg {
+                FnArg::Typed(typed) => Some(&typed.pat),
+                _ => None,
+            })
+            .collect::<Punctuated<_, Comma>>();
+
+        self.expr_arm_tokens(
+            wrapper,
+            &quote! { value },
+            &quote! { value.#function(#args) },
+        )
+    }
+
+    /// Generates a match arm binding the variant's value to `binding` and evaluating `body`.
+    pub(crate) fn expr_arm_tokens(
+        &self,
+        wrapper: impl ToTokens,
+        binding: &TokenStream,
+        body: &TokenStream,
     ) -> TokenStream {
         let ident = &self.ident;
+        quote! { #wrapper::#ident(#binding) => #body, }
+    }
+
+    /// Generates a match arm calling a trait method on the variant's value.
+    ///
+    /// With `trait_path` the call is fully qualified, so it can't be ambiguous when
+    /// the variant type implements several traits with a method of that name.
+    /// Otherwise method call syntax is used, which auto-dereferences types like
+    /// `Box<T>`. With `pinned` the value is passed as `Pin<&mut T>`.
+    #[allow(clippy::match_wildcard_for_single_variants)]
+    pub(crate) fn trait_call_arm_tokens(
+        &self,
+        wrapper: &Ident,
+        trait_path: Option<&Path>,
+        function: &Ident,
+        inputs: &Punctuated<FnArg, Comma>,
+        pinned: bool,
+    ) -> TokenStream {
+        let ident = &self.ident;
+        let ty = &self.ty;
         let args = inputs
             .iter()
             .filter_map(|arg| match arg {
@@ -73,8 +329,17 @@ impl Variant {
                 _ => None,
             })
             .collect::<Punctuated<_, Comma>>();
+        let value = if pinned {
+            quote! { ::core::pin::Pin::new(value) }
+        } else {
+            quote! { value }
+        };
 
-        quote! { #wrapper::#ident(value) => value.#function(#args), }
+        if let Some(trait_path) = trait_path {
+            quote! { #wrapper::#ident(value) => <#ty as #trait_path>::#function(#value, #args), }
+        } else {
+            quote! { #wrapper::#ident(value) => #value.#function(#args), }
+        }
     }
 
     /// Generates a match arm for retrieving the variant's type as a string.
@@ -138,7 +403,7 @@ impl Variant {
 
     /// Generates methods for accessing and iterating over variants in a `Vec`.
     ///
-    /// Generates methods:
+    /// Generates methods, with the default names of `naming`:
     /// - `first_varient`,
     /// - `first_variant_mut`,
     /// - `last_variant`,
@@ -160,48 +425,53 @@ impl Variant {
     //       - max & min (Ord)
     //
     #[allow(clippy::too_many_lines)]
-    pub(crate) fn vec_methods_tokens(&self, enum_ident: &Ident, vec_field: &Ident) -> TokenStream {
+    pub(crate) fn vec_methods_tokens(
+        &self,
+        enum_ident: &Ident,
+        vec_field: &Ident,
+        naming: &Naming,
+    ) -> TokenStream {
         let ident = &self.ident;
         let ty = &self.ty;
         let snake = self.ident_to_snake();
         let type_name = self.type_to_string();
 
-        let fn_first = Ident::new(&format!("first_{snake}"), ty.span());
+        let fn_first = fill(&naming.first, &snake, ty.span());
         let fn_first_doc = format!("Returns the first `{ident}` as `Option<&{type_name}>`.");
 
-        let fn_first_mut = Ident::new(&format!("first_{snake}_mut"), ty.span());
+        let fn_first_mut = fill(&naming.first, &format!("{snake}_mut"), ty.span());
         let fn_first_mut_doc =
             format!("Returns the first `{ident}` as `Option<&mut {type_name}>`.");
 
-        let fn_last = Ident::new(&format!("last_{snake}"), ty.span());
+        let fn_last = fill(&naming.last, &snake, ty.span());
         let fn_last_doc = format!("Returns the last `{ident}` as `Option<&{type_name}>`.");
 
-        let fn_last_mut = Ident::new(&format!("last_{snake}_mut"), ty.span());
+        let fn_last_mut = fill(&naming.last, &format!("{snake}_mut"), ty.span());
         let fn_last_mut_doc = format!("Returns the last `{ident}` as `Option<&mut {type_name}>`.");
 
-        let fn_iter = Ident::new(&format!("iter_{snake}"), ty.span());
+        let fn_iter = fill(&naming.iter, &snake, ty.span());
         let fn_iter_doc = format!("Returns an iterator over `{ident}` as `&{type_name}`.");
 
-        let fn_iter_mut = Ident::new(&format!("iter_{snake}_mut"), ty.span());
+        let fn_iter_mut = fill(&naming.iter, &format!("{snake}_mut"), ty.span());
         let fn_iter_mut_doc =
             format!("Returns a mutable iterator over `{ident}` as `&mut {type_name}`.");
 
-        let fn_enumerate = Ident::new(&format!("enumerate_{snake}"), ty.span());
+        let fn_enumerate = fill(&naming.enumerate, &snake, ty.span());
         let fn_enumerate_doc =
             format!("Returns an iterator over `{ident}` as (index, `&{type_name}`).");
 
-        let fn_enumerate_mut = Ident::new(&format!("enumerate_{snake}_mut"), ty.span());
+        let fn_enumerate_mut = fill(&naming.enumerate, &format!("{snake}_mut"), ty.span());
         let fn_enumerate_mut_doc =
             format!("Returns a mutable iterator over `{ident}` as (index, `&mut {type_name}`).");
 
-        let fn_count = Ident::new(&format!("count_{snake}"), ty.span());
+        let fn_count = fill(&naming.count, &snake, ty.span());
         let fn_count_doc = format!("Counts the number of `{ident}` variants in `{enum_ident}`.");
 
-        let fn_all = Ident::new(&format!("all_{snake}"), ty.span());
+        let fn_all = fill(&naming.all, &snake, ty.span());
         let fn_all_doc =
             format!("Returns true if all variants are `{ident}` variants in `{enum_ident}`.");
 
-        let fn_any = Ident::new(&format!("any_{snake}"), ty.span());
+        let fn_any = fill(&naming.any, &snake, ty.span());
         let fn_any_doc = format!("Returns true there is a `{ident}` variants in `{enum_ident}`.");
 
         quote! {
@@ -311,6 +581,51 @@ impl Variant {
         }
     }
 
+    /// Generates the `contains_variant` method for a `Vec` of an enum that implements `PartialEq`.
+    pub(crate) fn vec_contains_tokens(
+        &self,
+        enum_ident: &Ident,
+        vec_field: &Ident,
+        visibility: &Visibility,
+        naming: &Naming,
+    ) -> TokenStream {
+        let ident = &self.ident;
+        let ty = &self.ty;
+        let fn_contains = fill(&naming.contains, &self.ident_to_snake(), ty.span());
+        let fn_contains_doc = format!("Returns `true` if there is a `{ident}` equal to `value`.");
+
+        quote! {
+            #[doc = #fn_contains_doc]
+            #visibility fn #fn_contains(&self, value: &#ty) -> bool {
+                self.#vec_field.iter().any(|item| {
+                    ::std::matches!(item, #enum_ident::#ident(v) if <#ty as ::core::cmp::PartialEq>::eq(v, value))
+                })
+            }
+        }
+    }
+
+    /// Returns `true` if the variant's type is `'static` regardless of the
+    /// enum's generics: it has no type parameters, and no lifetimes or references
+    /// other than `'static` ones.
+    pub(crate) fn is_static(&self, generics: &Generics) -> bool {
+        fn visit(tokens: TokenStream, generics: &Generics) -> bool {
+            let tokens = tokens.into_iter().collect::<Vec<_>>();
+            tokens.iter().enumerate().all(|(i, token)| match token {
+                // `&` must be followed by a `'static` lifetime
+                TokenTree::Punct(punct) if punct.as_char() == '&' => {
+                    matches!(tokens.get(i + 1), Some(TokenTree::Punct(p)) if p.as_char() == '\'')
+                }
+                TokenTree::Punct(punct) if punct.as_char() == '\'' => {
+                    matches!(tokens.get(i + 1), Some(TokenTree::Ident(ident)) if ident == "static")
+                }
+                TokenTree::Punct(_) | TokenTree::Literal(_) => true,
+                TokenTree::Ident(ident) => !generics.type_params().any(|p| p.ident == *ident),
+                TokenTree::Group(group) => visit(group.stream(), generics),
+            })
+        }
+        visit(self.ty.to_token_stream(), generics)
+    }
+
     /// Converts the variant's type to a string representation.
     ///
     /// Cleans up the token stream to remove unnecessary spaces and format references.
@@ -326,9 +641,97 @@ impl Variant {
             .replace(" >", ">")
     }
 
+    /// Returns the variant's type without lifetimes, as written in the type match macro:
+    /// `&'a str` becomes `&str` and `Str<'a>` becomes `Str`.
+    pub(crate) fn type_without_lifetimes(&self) -> Type {
+        struct RemoveLifetimes;
+
+        impl VisitMut for RemoveLifetimes {
+            fn visit_type_reference_mut(&mut self, reference: &mut TypeReference) {
+                reference.lifetime = None;
+                syn::visit_mut::visit_type_reference_mut(self, reference);
+            }
+
+            fn visit_path_arguments_mut(&mut self, arguments: &mut PathArguments) {
+                if let PathArguments::AngleBracketed(args) = arguments {
+                    args.args = std::mem::take(&mut args.args)
+                        .into_iter()
+                        .filter(|arg| !matches!(arg, GenericArgument::Lifetime(_)))
+                        .collect();
+                    if args.args.is_empty() {
+                        *arguments = PathArguments::None;
+                    }
+                }
+                syn::visit_mut::visit_path_arguments_mut(self, arguments);
+            }
+        }
+
+        let mut ty = self.ty.clone();
+        RemoveLifetimes.visit_type_mut(&mut ty);
+        ty
+    }
+
+    /// Returns the value of `#[tag = n]`, if set.
+    pub(crate) fn tag_value(&self) -> Option<u32> {
+        self.tag.as_ref().and_then(|tag| tag.base10_parse().ok())
+    }
+
+    /// Returns the `#[meta]` value for `key`, if set.
+    pub(crate) fn meta_value(&self, key: &Ident) -> Option<&LitStr> {
+        self.meta
+            .iter()
+            .find_map(|(other, value)| (other == key).then_some(value))
+    }
+
+    /// Returns the `#[serde(rename = "..")]` of the variant, the only `serde`
+    /// attribute `impl serde` supports.
+    pub(crate) fn serde_rename(&self) -> syn::Result<Option<LitStr>> {
+        let mut rename = None;
+        for attr in self
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("serde"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    rename = Some(meta.value()?.parse::<LitStr>()?);
+                    Ok(())
+                } else {
+                    Err(meta.error("`impl serde` only supports `#[serde(rename = \"..\")]`"))
+                }
+            })?;
+        }
+        Ok(rename)
+    }
+
+    /// Returns the name of the variant for `impl serde`: `#[serde(rename = "..")]`,
+    /// the `#[tag = n]` or the type name.
+    ///
+    /// Invalid `serde` attributes are reported when parsing the enum.
+    pub(crate) fn serde_name(&self) -> String {
+        self.serde_rename()
+            .unwrap_or_default()
+            .map(|rename| rename.value())
+            .or_else(|| self.tag_value().map(|tag| tag.to_string()))
+            .unwrap_or_else(|| self.type_to_string())
+    }
+
     /// Converts the variant's identifier to `snake_case`.
     pub(crate) fn ident_to_snake(&self) -> String {
-        camel_to_snake(&self.ident.to_string())
+        self.snake
+            .clone()
+            .unwrap_or_else(|| camel_to_snake(&self.ident.to_string()))
+    }
+
+    /// Returns the `snake_case` identifier of the variant as a field name, raw for keywords.
+    ///
+    /// `crate`, `self`, `super` and `Self` can't be raw identifiers and get a `_` suffix.
+    pub(crate) fn snake_field_ident(&self) -> Ident {
+        let name = self.ident_to_snake();
+        if matches!(name.as_str(), "crate" | "self" | "super" | "Self") {
+            return Ident::new(&format!("{name}_"), self.ident.span());
+        }
+        syn::parse_str::<Ident>(&name).unwrap_or_else(|_| Ident::new_raw(&name, self.ident.span()))
     }
 }
 
@@ -347,6 +750,44 @@ impl Parse for Variant {
         let (into, other_attrs): (Vec<_>, Vec<_>) = attrs
             .into_iter()
             .partition(|attr| attr.path().is_ident("into"));
+        let (parse, other_attrs): (Vec<_>, Vec<_>) = other_attrs
+            .into_iter()
+            .partition(|attr| attr.path().is_ident("parse"));
+        let mut parse_skip = false;
+        let mut parse_priority = 0;
+        let (display, other_attrs): (Vec<_>, Vec<_>) = other_attrs
+            .into_iter()
+            .partition(|attr| attr.path().is_ident("display"));
+        let display = parse_single_lit(&display)?;
+        let (context, other_attrs): (Vec<_>, Vec<_>) = other_attrs
+            .into_iter()
+            .partition(|attr| attr.path().is_ident("context"));
+        let context = parse_single_lit(&context)?;
+        let (tag, other_attrs): (Vec<_>, Vec<_>) = other_attrs
+            .into_iter()
+            .partition(|attr| attr.path().is_ident("tag"));
+        let tag = tag.last().map(parse_tag).transpose()?;
+        let (nodyn, other_attrs): (Vec<_>, Vec<_>) = other_attrs
+            .into_iter()
+            .partition(|attr| attr.path().is_ident("nodyn"));
+        let snake = parse_snake(&nodyn)?;
+        let (meta_attrs, other_attrs): (Vec<_>, Vec<_>) = other_attrs
+            .into_iter()
+            .partition(|attr| attr.path().is_ident("meta"));
+        let meta = parse_meta(&meta_attrs)?;
+        for attr in parse {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    parse_skip = true;
+                    Ok(())
+                } else if meta.path.is_ident("priority") {
+                    parse_priority = meta.value()?.parse::<LitInt>()?.base10_parse()?;
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `skip` or `priority = n`"))
+                }
+            })?;
+        }
         let into_types = into
             .into_iter()
             .flat_map(|attr| {
@@ -361,23 +802,118 @@ impl Parse for Variant {
             into: into_types,
             ident,
             ty,
+            parse_skip,
+            parse_priority,
+            display,
+            context,
+            tag,
+            meta,
+            snake,
         })
     }
 }
 
+/// Parses an attribute like `#[display("..")]` that may only be given once.
+fn parse_single_lit(attrs: &[Attribute]) -> syn::Result<Option<LitStr>> {
+    if let Some(duplicate) = attrs.get(1) {
+        let name = duplicate.path().to_token_stream();
+        return Err(syn::Error::new_spanned(
+            duplicate,
+            format!("duplicate `#[{name}]` attribute"),
+        ));
+    }
+    attrs
+        .first()
+        .map(Attribute::parse_args::<LitStr>)
+        .transpose()
+}
+
+/// Parses `#[tag = n]`, where `n` must fit in a `u32`.
+fn parse_tag(attr: &Attribute) -> syn::Result<LitInt> {
+    if let Meta::NameValue(MetaNameValue {
+        value: Expr::Lit(ExprLit {
+            lit: Lit::Int(lit), ..
+        }),
+        ..
+    }) = &attr.meta
+    {
+        lit.base10_parse::<u32>().map_err(|error| {
+            syn::Error::new_spanned(lit, format!("expected a `u32` tag, {error}"))
+        })?;
+        Ok(lit.clone())
+    } else {
+        Err(syn::Error::new_spanned(attr, "expected `#[tag = n]`"))
+    }
+}
+
+/// Parses `#[nodyn(snake = "..")]`, the `snake_case` name of the variant.
+fn parse_snake(attrs: &[Attribute]) -> syn::Result<Option<String>> {
+    let mut snake = None;
+    for attr in attrs {
+        attr.parse_nested_meta(|meta| {
+            if !meta.path.is_ident("snake") {
+                return Err(meta.error("expected `snake = \"..\"`"));
+            }
+            let value = meta.value()?.parse::<LitStr>()?;
+            if value.value().is_empty() || !is_ident(&format!("x_{}", value.value())) {
+                return Err(syn::Error::new_spanned(
+                    value,
+                    "expected a `snake_case` name",
+                ));
+            }
+            snake = Some(value.value());
+            Ok(())
+        })?;
+    }
+    Ok(snake)
+}
+
+/// Names of the items generated for the kind, which would clash with `#[meta]` keys.
+const RESERVED_META_KEYS: [&str; 6] = ["kind", "tag", "from_tag", "TAGS", "META", "META_KEYS"];
+
+/// Parses `#[meta(key = "value", ..)]`, rejecting duplicate and reserved keys.
+fn parse_meta(attrs: &[Attribute]) -> syn::Result<Vec<(Ident, LitStr)>> {
+    let mut meta = Vec::<(Ident, LitStr)>::new();
+    for attr in attrs {
+        attr.parse_nested_meta(|nested| {
+            let Some(key) = nested.path.get_ident().cloned() else {
+                return Err(nested.error("expected `key = \"value\"`"));
+            };
+            let value = nested.value()?.parse::<LitStr>()?;
+            if RESERVED_META_KEYS.iter().any(|reserved| key == reserved) {
+                return Err(syn::Error::new_spanned(
+                    &key,
+                    format!("`{key}` can't be a `#[meta]` key, it is a generated item"),
+                ));
+            }
+            if meta.iter().any(|(other, _)| *other == key) {
+                return Err(syn::Error::new_spanned(
+                    &key,
+                    format!("duplicate `#[meta]` key `{key}`"),
+                ));
+            }
+            meta.push((key, value));
+            Ok(())
+        })?;
+    }
+    Ok(meta)
+}
+
+/// Converts a `CamelCase` name to `snake_case`, keeping acronyms together, so
+/// `HTTPResponse` becomes `http_response`.
 pub(crate) fn camel_to_snake(camel: &str) -> String {
+    let chars = camel.chars().collect::<Vec<_>>();
     let mut snake = String::new();
-    let mut first = true;
-    for c in camel.chars() {
-        if c.is_uppercase() {
-            if !first {
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let after_word = !chars[i - 1].is_uppercase() && chars[i - 1] != '_';
+            let ends_acronym = chars[i - 1].is_uppercase()
+                && chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+            if after_word || ends_acronym {
                 snake.push('_');
             }
-            snake.push_str(&c.to_lowercase().to_string());
-        } else {
-            snake.push(c);
         }
-        first = false;
+        snake.extend(c.to_lowercase());
     }
     snake
 }
@@ -520,13 +1056,19 @@ fn ident_from_type(ty: &Type) -> syn::Result<Ident> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::naming::Case;
     use syn::parse_str;
 
     #[test]
     fn test_camel_to_snake() {
         assert_eq!(camel_to_snake("MyVariant"), "my_variant");
-        assert_eq!(camel_to_snake("HTTPResponse"), "h_t_t_p_response");
+        assert_eq!(camel_to_snake("HTTPResponse"), "http_response");
+        assert_eq!(camel_to_snake("ResponseHTTP"), "response_http");
+        assert_eq!(camel_to_snake("VecU8"), "vec_u8");
+        assert_eq!(camel_to_snake("I32"), "i32");
+        assert_eq!(camel_to_snake("U8Array"), "u8_array");
         assert_eq!(camel_to_snake("lowercase"), "lowercase");
+        assert_eq!(Case::Snake.to_snake("HTTPResponse"), "h_t_t_p_response");
     }
 
     #[test]
@@ -542,6 +1084,20 @@ mod tests {
         assert_eq!(input.into.len(), 1);
     }
 
+    #[test]
+    fn test_variant_parse_attribute() {
+        let input = parse_str::<Variant>("#[parse(priority = 2)] i32").unwrap();
+        assert!(!input.parse_skip);
+        assert_eq!(input.parse_priority, 2);
+        assert!(input.attrs.is_empty());
+
+        let input = parse_str::<Variant>("#[parse(skip)] String").unwrap();
+        assert!(input.parse_skip);
+        assert_eq!(input.parse_priority, 0);
+
+        assert!(parse_str::<Variant>("#[parse(first)] String").is_err());
+    }
+
     #[test]
     fn test_ident_from_type() {
         let ty: Type = parse_str("std::string::String").unwrap();
@@ -561,6 +1117,33 @@ mod tests {
         assert_eq!(ident.to_string(), "I32StringTuple");
     }
 
+    #[test]
+    fn test_is_static() {
+        let generics: Generics = syn::parse_quote! { <'a, T> };
+        let is_static = |ty: &str| parse_str::<Variant>(ty).unwrap().is_static(&generics);
+        assert!(is_static("i32"));
+        assert!(is_static("Vec<String>"));
+        assert!(!is_static("&'a str"));
+        assert!(is_static("&'static str"));
+        assert!(is_static("Cow<'static, [u8]>"));
+        assert!(!is_static("&'static &'a str"));
+        assert!(!is_static("Vec<T>"));
+        assert!(!is_static("Cow<'a, str>"));
+        assert!(is_static("Tree"));
+    }
+
+    #[test]
+    fn test_type_without_lifetimes() {
+        let without = |ty: &str| {
+            let ty = parse_str::<Variant>(ty).unwrap().type_without_lifetimes();
+            ty.to_token_stream().to_string()
+        };
+        assert_eq!(without("&'a str"), "& str");
+        assert_eq!(without("Str<'a>"), "Str");
+        assert_eq!(without("Cow<'a, str>"), "Cow < str >");
+        assert_eq!(without("Vec<&'static [u8]>"), "Vec < & [u8] >");
+    }
+
     #[test]
     fn test_type_to_string() {
         let variant = Variant {
@@ -568,6 +1151,13 @@ mod tests {
             into: vec![],
             ident: Ident::new("Test", proc_macro2::Span::call_site()),
             ty: parse_str::<Type>("&str").unwrap(),
+            parse_skip: false,
+            parse_priority: 0,
+            display: None,
+            context: None,
+            tag: None,
+            meta: Vec::new(),
+            snake: None,
         };
         assert_eq!(variant.type_to_string(), "&str");
 
@@ -576,6 +1166,13 @@ mod tests {
             into: vec![],
             ident: Ident::new("Test", proc_macro2::Span::call_site()),
             ty: parse_str::<Type>("Vec<i32>").unwrap(),
+            parse_skip: false,
+            parse_priority: 0,
+            display: None,
+            context: None,
+            tag: None,
+            meta: Vec::new(),
+            snake: None,
         };
         assert_eq!(variant.type_to_string(), "Vec<i32>");
     }
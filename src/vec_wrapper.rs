@@ -9,7 +9,7 @@ use syn::{
     punctuated::Punctuated,
 };
 
-use crate::{GenericsExt, NodynEnum, camel_to_snake};
+use crate::{GenericsExt, NodynEnum, naming::Naming};
 
 #[derive(Debug, Clone)]
 pub(crate) struct StandardVecWrapper {
@@ -42,10 +42,9 @@ impl StandardVecWrapper {
         enum_ident: &Ident,
         generics: &Generics,
         derive_attr: &[Attribute],
+        naming: &Naming,
     ) -> VecWrapper {
-        let ident = self
-            .ident
-            .unwrap_or_else(|| format_ident!("{}Vec", enum_ident));
+        let ident = self.ident.unwrap_or_else(|| naming.vec_ident(enum_ident));
         let defined_attrs = self.attrs;
         let stripped_attrs = strip_copy(derive_attr);
         let wrapper: ItemStruct = parse_quote! {
@@ -138,6 +137,8 @@ impl VecWrapper {
         let partial_ord = &self.with_partial_ord_tokens(nodyn);
         let ord = &self.with_ord_tokens(nodyn);
         let copy = &self.with_copy_tokens(nodyn);
+        let serde = &self.serde_tokens(nodyn);
+        let codec = &self.codec_tokens(nodyn);
         quote! {
             #wrapper_struct
             #impls
@@ -148,6 +149,125 @@ impl VecWrapper {
             #partial_ord
             #ord
             #copy
+            #serde
+            #codec
+        }
+    }
+
+    /// Generates `Serialize` and `Deserialize` as a plain sequence of the enum, when
+    /// the enum uses `impl serde`.
+    ///
+    /// `Deserialize` requires `Default` for the other fields of a custom wrapper.
+    fn serde_tokens(&self, nodyn: &NodynEnum) -> TokenStream {
+        if nodyn.optional_impl.serde.is_none() {
+            return TokenStream::new();
+        }
+        let ident = &self.definition.ident;
+        let field = &self.vec_field;
+        let enum_ident = &nodyn.ident;
+        let enum_generics = nodyn.generics_tokens();
+        let type_generics = self.merged_type_generics_tokens(nodyn);
+        let default_fields = self.default_fields();
+
+        let serialize_w: WherePredicate = parse_quote! {
+            #enum_ident #enum_generics: ::serde::Serialize
+        };
+        let (serialize_generics, serialize_where) =
+            self.merge_generics(nodyn, &Generics::default(), &serialize_w);
+        let serialize = quote! {
+            impl #serialize_generics ::serde::Serialize for #ident #type_generics #serialize_where {
+                fn serialize<S: ::serde::Serializer>(
+                    &self,
+                    serializer: S,
+                ) -> ::core::result::Result<S::Ok, S::Error> {
+                    ::serde::Serialize::serialize(&self.#field, serializer)
+                }
+            }
+        };
+        if !is_trait_derived(&self.definition.attrs, "Default") {
+            return serialize;
+        }
+
+        let de = nodyn.generics.new_lifetime();
+        let deserialize_g: Generics = parse_quote! { <#de> };
+        let deserialize_w: WherePredicate = parse_quote! {
+            #enum_ident #enum_generics: ::serde::Deserialize<#de>
+        };
+        let (deserialize_generics, deserialize_where) =
+            self.merge_generics(nodyn, &deserialize_g, &deserialize_w);
+        quote! {
+            #serialize
+
+            impl #deserialize_generics ::serde::Deserialize<#de> for #ident #type_generics #deserialize_where {
+                fn deserialize<D: ::serde::Deserializer<#de>>(
+                    deserializer: D,
+                ) -> ::core::result::Result<Self, D::Error> {
+                    ::core::result::Result::Ok(Self {
+                        #field: ::serde::Deserialize::deserialize(deserializer)?,
+                        #default_fields
+                    })
+                }
+            }
+        }
+    }
+
+    /// Implements `NodynEncode` for the wrapper, as the length followed by the items, when
+    /// the enum uses `impl codec`. `decode` needs the wrapper to derive `Default`.
+    fn codec_tokens(&self, nodyn: &NodynEnum) -> TokenStream {
+        if !nodyn.optional_impl.codec {
+            return TokenStream::new();
+        }
+        let ident = &self.definition.ident;
+        let field = &self.vec_field;
+        let enum_ident = &nodyn.ident;
+        let enum_generics = nodyn.generics_tokens();
+        let type_generics = self.merged_type_generics_tokens(nodyn);
+        let visibility = &self.definition.vis;
+        let codec_w: WherePredicate = parse_quote! {
+            #enum_ident #enum_generics: NodynEncode
+        };
+        let (generics, where_clause) = self.merge_generics(nodyn, &Generics::default(), &codec_w);
+
+        let encode = quote! {
+            /// Appends the length and the encoded items to `out`.
+            #visibility fn encode(&self, out: &mut ::std::vec::Vec<u8>) {
+                NodynEncode::encode(&self.#field, out);
+            }
+        };
+        if !is_trait_derived(&self.definition.attrs, "Default") {
+            return quote! {
+                impl #generics #ident #type_generics #where_clause {
+                    #encode
+                }
+            };
+        }
+
+        let default_fields = self.default_fields();
+        quote! {
+            impl #generics NodynEncode for #ident #type_generics #where_clause {
+                fn encode(&self, out: &mut ::std::vec::Vec<u8>) {
+                    NodynEncode::encode(&self.#field, out);
+                }
+
+                fn decode(input: &[u8]) -> ::core::result::Result<(Self, usize), NodynDecodeError> {
+                    let (#field, read) = NodynEncode::decode(input)?;
+                    ::core::result::Result::Ok((Self { #field, #default_fields }, read))
+                }
+            }
+
+            impl #generics #ident #type_generics #where_clause {
+                #encode
+
+                /// Decodes the length and the items from the start of `input`, returning
+                /// the wrapper with the number of bytes read.
+                ///
+                /// # Errors
+                ///
+                /// Returns an error if `input` ends early or holds an invalid item.
+                #visibility fn decode(input: &[u8]) -> ::core::result::Result<(Self, usize), NodynDecodeError> {
+                    <Self as NodynEncode>::decode(input)
+                }
+            }
         }
     }
 
@@ -196,6 +316,7 @@ impl VecWrapper {
         let partial_eq_methods = &self.partial_eq_methods_tokens(nodyn);
         let field = &self.vec_field;
         let variant_methods = nodyn.variant_vec_tokens(field);
+        let member_methods = nodyn.member_vec_tokens(field);
         let type_generics = self.merged_type_generics_tokens(nodyn);
 
         quote! {
@@ -205,6 +326,7 @@ impl VecWrapper {
                 #modified_methods
                 #partial_eq_methods
                 #variant_methods
+                #member_methods
             }
         }
     }
@@ -661,12 +783,17 @@ impl VecWrapper {
     /// Generates methods that require the `PartialEq` trait.
     ///
     /// - [`dedup`][Vec::dedup]: Removes consecutive duplicate elements.
+    /// - `contains_variant`: Checks for a variant holding the given value.
     fn partial_eq_methods_tokens(&self, nodyn: &NodynEnum) -> TokenStream {
-        if !is_trait_derived(&nodyn.attrs, "PartialEq") {
+        if !nodyn.has_partial_eq() {
             return TokenStream::new();
         }
         let field = &self.vec_field;
         let visibility = &self.definition.vis;
+        let contains = nodyn
+            .variants
+            .iter()
+            .map(|v| v.vec_contains_tokens(&nodyn.ident, field, visibility, &nodyn.naming));
 
         quote! {
             /// Removes consecutive duplicate elements.
@@ -675,6 +802,8 @@ impl VecWrapper {
             #visibility fn dedup(&mut self) {
                 self.#field.dedup();
             }
+
+            #(#contains)*
         }
     }
 
@@ -997,7 +1126,10 @@ impl VecWrapper {
         let ident = &self.definition.ident;
         let field = &self.vec_field;
         let enum_ident = &nodyn.ident;
-        let snake_ident = Ident::new(&camel_to_snake(&ident.to_string()), ident.span());
+        let snake_ident = Ident::new(
+            &nodyn.naming.case.to_snake(&ident.to_string()),
+            ident.span(),
+        );
         let (macro_vec, macro_enum) = if let Some(path) = &nodyn.module_path {
             (quote! { ::#path::#ident }, quote! { ::#path::#enum_ident })
         } else {
@@ -1097,10 +1229,11 @@ impl VecWrapper {
         }
     }
 
-    /// Generates methods that require the enum to have `#[derive(PartialOrd)]`;
+    /// Generates methods that require the enum to have `#[derive(PartialOrd)]`
+    /// or `impl compare`;
     /// - [`is_sorted`][Vec::is_sorted]
     fn with_partial_ord_tokens(&self, nodyn: &NodynEnum) -> TokenStream {
-        if !is_trait_derived(&nodyn.attrs, "PartialOrd") {
+        if !nodyn.has_partial_ord() {
             return TokenStream::new();
         }
         let field = &self.vec_field;
@@ -1121,13 +1254,13 @@ impl VecWrapper {
         }
     }
 
-    /// Generates methods that require the enum to have `#[derive(Ord)]`;
+    /// Generates methods that require the enum to have `#[derive(Ord)]` or `impl transparent(Ord)`;
     ///
     /// - [`sort`][Vec::sort]
     /// - [`sort_unstable`][Vec::sort_unstable]
     /// - [`binary_search`][Vec::binary_search]
     fn with_ord_tokens(&self, nodyn: &NodynEnum) -> TokenStream {
-        if !is_trait_derived(&nodyn.attrs, "Ord") {
+        if !nodyn.has_ord() {
             return TokenStream::new();
         }
         let field = &self.vec_field;
@@ -1289,7 +1422,7 @@ fn strip_copy(attrs: &[Attribute]) -> Vec<Attribute> {
         .collect()
 }
 
-fn is_trait_derived(attributes: &[Attribute], trait_name: &str) -> bool {
+pub(crate) fn is_trait_derived(attributes: &[Attribute], trait_name: &str) -> bool {
     let parser = Punctuated::<Ident, Token![,]>::parse_terminated;
     for attr in attributes {
         if let Meta::List(list) = &attr.meta {
@@ -0,0 +1,33 @@
+use nodyn::nodyn;
+
+pub trait Shape {
+    fn area(&self) -> f64;
+}
+
+#[derive(Debug)]
+pub struct Square(f64);
+
+impl Shape for Square {
+    fn area(&self) -> f64 {
+        self.0 * self.0
+    }
+}
+
+static UNIT: Square = Square(1.0);
+
+nodyn! {
+    // delegated calls auto-deref the wrapped value, like a method call
+    pub enum Shapes {
+        Boxed(Box<Square>),
+        Borrowed(&'static Square),
+    }
+
+    impl Shape {
+        fn area(&self) -> f64;
+    }
+}
+
+fn main() {
+    assert_eq!(Shapes::from(Box::new(Square(2.0))).area(), 4.0);
+    assert_eq!(Shapes::from(&UNIT).area(), 1.0);
+}
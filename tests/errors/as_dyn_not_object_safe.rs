@@ -0,0 +1,23 @@
+use nodyn::nodyn;
+
+pub trait Named {
+    fn named(name: &str) -> Self;
+}
+
+impl Named for i32 {
+    fn named(name: &str) -> Self {
+        name.len() as i32
+    }
+}
+
+nodyn! {
+    pub enum Value {
+        i32,
+    }
+    impl Named {
+        fn named(name: &str) -> Self;
+    }
+    impl as_dyn;
+}
+
+fn main() {}
@@ -0,0 +1,25 @@
+use nodyn::nodyn;
+
+pub trait Visit {
+    fn name(&self) -> String;
+
+    fn visit<T>(&self, _value: T) {}
+}
+
+impl Visit for i32 {
+    fn name(&self) -> String {
+        self.to_string()
+    }
+}
+
+nodyn! {
+    pub enum Value {
+        i32,
+    }
+    impl Visit {
+        fn name(&self) -> String;
+    }
+    impl as_dyn;
+}
+
+fn main() {}
@@ -0,0 +1,22 @@
+use nodyn::nodyn;
+
+nodyn! {
+    enum Unused {
+        #[display("int {}")]
+        i32,
+        #[context("reading")]
+        String,
+    }
+}
+
+nodyn! {
+    enum Duplicate {
+        #[display("int {}")]
+        #[display("integer {}")]
+        i32,
+        String,
+    }
+    impl transparent(Display);
+}
+
+fn main() {}
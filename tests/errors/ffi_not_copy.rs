@@ -0,0 +1,17 @@
+nodyn::nodyn! {
+    pub enum Owned {
+        i32,
+        String,
+    }
+    impl ffi;
+}
+
+nodyn::nodyn! {
+    pub enum Generic<T> {
+        i32,
+        T,
+    }
+    impl ffi;
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+#[derive(Clone, Copy)]
+pub struct NotReprC {
+    pub x: i32,
+}
+
+nodyn::nodyn! {
+    #[derive(Clone, Copy)]
+    pub enum Value {
+        char,
+        NotReprC,
+    }
+    impl ffi;
+}
+
+fn main() {}
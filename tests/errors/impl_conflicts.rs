@@ -0,0 +1,20 @@
+nodyn::nodyn! {
+    #[derive(Debug)]
+    pub enum AppError {
+        std::io::Error,
+        std::num::ParseIntError,
+    }
+    impl Error;
+    impl transparent(Display);
+}
+
+nodyn::nodyn! {
+    #[derive(Debug, Clone)]
+    pub enum Number {
+        i32,
+        i64,
+    }
+    impl compare transparent(Hash, Eq, Ord);
+}
+
+fn main() {}
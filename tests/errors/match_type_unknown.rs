@@ -0,0 +1,14 @@
+nodyn::nodyn! {
+    pub enum Value {
+        i32,
+        String,
+    }
+    impl match_type;
+}
+
+fn main() {
+    let value = Value::from(1);
+    let _ = value_match!(value, i32(n) => n, u8(n) => n.into());
+    let value = Value::from(1);
+    let _ = value_match!(value, i32(n) => { n } Vec<u8>(bytes) if bytes.is_empty() => { 0 });
+}
@@ -0,0 +1,32 @@
+nodyn::nodyn! {
+    #[derive(Debug)]
+    pub enum Value {
+        i32,
+        String,
+    }
+    impl is_as;
+}
+
+struct Other;
+
+impl ValueMember for Other {
+    const INDEX: usize = 2;
+
+    fn wrap(self) -> Value {
+        Value::I32(0)
+    }
+
+    fn get<'a>(_value: &'a Value) -> Option<&'a Self> {
+        None
+    }
+
+    fn get_mut<'a>(_value: &'a mut Value) -> Option<&'a mut Self> {
+        None
+    }
+
+    fn unwrap_from(value: Value) -> Result<Self, Value> {
+        Err(value)
+    }
+}
+
+fn main() {}
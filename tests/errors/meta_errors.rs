@@ -0,0 +1,20 @@
+nodyn::nodyn! {
+    pub enum Reserved {
+        #[meta(tag = "a")]
+        u8,
+        #[meta(kind = "b")]
+        u16,
+    }
+}
+
+nodyn::nodyn! {
+    pub enum Untagged {
+        #[meta(label = "a")]
+        u8,
+    }
+}
+
+fn main() {
+    // `#[meta]` alone doesn't generate tags
+    let _ = Untagged::from(1u8).tag();
+}
@@ -0,0 +1,33 @@
+pub struct HTTPResponse;
+pub struct HttpResponse;
+
+nodyn::nodyn! {
+    pub enum Acronyms {
+        HTTPResponse,
+        HttpResponse,
+    }
+}
+
+nodyn::nodyn! {
+    pub enum Overridden {
+        i32,
+        #[nodyn(snake = "i32")]
+        i64,
+    }
+}
+
+nodyn::nodyn! {
+    #[nodyn(is = "r#is_{}")]
+    pub enum Raw {
+        i32,
+    }
+}
+
+nodyn::nodyn! {
+    #[nodyn(first_mut = "head_{}_mut")]
+    pub enum Unknown {
+        i32,
+    }
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+nodyn::nodyn! {
+    pub enum Value {
+        #[serde(rename = "number", alias = "int")]
+        i32,
+        #[serde(skip)]
+        String,
+        #[serde(rename = 1)]
+        bool,
+    }
+    impl serde;
+}
+
+fn main() {}
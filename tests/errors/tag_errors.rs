@@ -0,0 +1,25 @@
+nodyn::nodyn! {
+    pub enum Missing {
+        #[tag = 1]
+        i32,
+        String,
+    }
+}
+
+nodyn::nodyn! {
+    pub enum Duplicate {
+        #[tag = 1]
+        i32,
+        #[tag = 1]
+        String,
+    }
+}
+
+nodyn::nodyn! {
+    pub enum Negative {
+        #[tag = -1]
+        i32,
+    }
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+nodyn::nodyn! {
+    pub enum Number {
+        i32,
+        f64,
+    }
+    impl transparent(Eq);
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+pub trait Consume {
+    fn consume(self: Box<Self>) -> u32;
+}
+
+nodyn::nodyn! {
+    pub enum Value {
+        i32,
+        String,
+    }
+    impl Consume {
+        fn consume(self: Box<Self>) -> u32;
+    }
+}
+
+fn main() {}
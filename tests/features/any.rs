@@ -0,0 +1,75 @@
+use std::any::{Any, TypeId};
+
+use nodyn::nodyn;
+
+nodyn! {
+    #[derive(Debug, PartialEq)]
+    pub enum Value {
+        i32,
+        String,
+    }
+    impl introspection;
+}
+
+nodyn! {
+    #[derive(Debug)]
+    pub enum Borrowed<'a> {
+        i32,
+        &'a str,
+    }
+    impl introspection;
+}
+
+nodyn! {
+    #[derive(Debug, PartialEq)]
+    pub enum Label {
+        i32,
+        &'static str,
+    }
+    impl introspection;
+}
+
+nodyn! {
+    #[derive(Debug)]
+    pub enum Generic<T> {
+        u8,
+        Vec<T>,
+    }
+    impl introspection;
+}
+
+fn main() {
+    let boxed: Box<dyn Any> = Box::new(42i32);
+    assert_eq!(Value::from_any(boxed).unwrap(), Value::I32(42));
+    let boxed: Box<dyn Any> = Box::new("hi".to_string());
+    assert_eq!(Value::from_any(boxed).unwrap(), Value::String("hi".to_string()));
+
+    let boxed: Box<dyn Any> = Box::new(1.5f64);
+    let rejected = Value::from_any(boxed).unwrap_err();
+    assert_eq!(rejected.downcast_ref::<f64>(), Some(&1.5));
+
+    let value = Value::from(7);
+    assert_eq!(value.as_any().downcast_ref::<i32>(), Some(&7));
+    assert!(value.as_any().downcast_ref::<String>().is_none());
+    assert_eq!(value.value_type_id(), TypeId::of::<i32>());
+    assert_eq!(Any::type_id(&value), TypeId::of::<Value>());
+
+    // variants with lifetimes are skipped
+    let boxed: Box<dyn Any> = Box::new(3i32);
+    assert!(matches!(Borrowed::from_any(boxed), Ok(Borrowed::I32(3))));
+    let boxed: Box<dyn Any> = Box::new("str");
+    assert!(Borrowed::from_any(boxed).is_err());
+    let text: Borrowed<'static> = "static".into();
+    assert_eq!(text.value_type_id(), TypeId::of::<&str>());
+
+    // `'static` references are tried
+    let boxed: Box<dyn Any> = Box::new("label");
+    assert_eq!(Label::from_any(boxed).unwrap(), Label::from("label"));
+
+    // variants with type parameters are skipped
+    let boxed: Box<dyn Any> = Box::new(vec![1u8]);
+    assert!(Generic::<u8>::from_any(boxed).is_err());
+    let boxed: Box<dyn Any> = Box::new(9u8);
+    assert!(matches!(Generic::<u8>::from_any(boxed), Ok(Generic::U8(9))));
+    assert_eq!(Generic::<u8>::from(vec![1u8]).value_type_id(), TypeId::of::<Vec<u8>>());
+}
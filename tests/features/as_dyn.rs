@@ -0,0 +1,112 @@
+use std::fmt::{self, Display};
+
+use nodyn::nodyn;
+
+pub trait Shape {
+    fn area(&self) -> f64;
+    fn scale(&mut self, factor: f64);
+}
+
+#[derive(Debug)]
+pub struct Square(f64);
+
+impl Shape for Square {
+    fn area(&self) -> f64 {
+        self.0 * self.0
+    }
+    fn scale(&mut self, factor: f64) {
+        self.0 *= factor;
+    }
+}
+
+#[derive(Debug)]
+pub struct Circle(f64);
+
+impl Shape for Circle {
+    fn area(&self) -> f64 {
+        3.0 * self.0 * self.0
+    }
+    fn scale(&mut self, factor: f64) {
+        self.0 *= factor;
+    }
+}
+
+impl Display for Square {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "square {}", self.0)
+    }
+}
+
+impl Display for Circle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "circle {}", self.0)
+    }
+}
+
+nodyn! {
+    pub enum AnyShape {
+        Square,
+        Circle,
+    }
+    impl Shape {
+        fn area(&self) -> f64;
+        fn scale(&mut self, factor: f64);
+    }
+    impl Display {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+    }
+    impl as_dyn;
+}
+
+nodyn! {
+    pub enum Numbers {
+        std::ops::Range<u32>,
+        std::vec::IntoIter<u32>,
+    }
+    impl Iterator<Item = u32> + DoubleEndedIterator;
+    impl as_dyn(Iterator, DoubleEndedIterator);
+}
+
+nodyn! {
+    pub enum Text<'a, 'b> {
+        &'a str,
+        std::borrow::Cow<'b, str>,
+    }
+    impl Display {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+    }
+    impl as_dyn;
+}
+
+fn texts<'a>(word: &'a str, cow: &'a str) -> Vec<Box<dyn Display + 'a>> {
+    vec![Text::from(word).into(), Text::from(std::borrow::Cow::Borrowed(cow)).into()]
+}
+
+fn total_area(shapes: &[&dyn Shape]) -> f64 {
+    shapes.iter().map(|s| s.area()).sum()
+}
+
+fn main() {
+    let mut square = AnyShape::from(Square(2.0));
+    let circle = AnyShape::from(Circle(1.0));
+    assert_eq!(total_area(&[square.as_dyn_shape(), circle.as_dyn_shape()]), 7.0);
+
+    square.as_dyn_shape_mut().scale(2.0);
+    assert_eq!(square.area(), 16.0);
+    assert_eq!(square.as_dyn_display().to_string(), "square 4");
+
+    let boxed: Vec<Box<dyn Shape>> = vec![square.into(), circle.into()];
+    assert_eq!(boxed.iter().map(|s| s.area()).sum::<f64>(), 19.0);
+    let boxed: Box<dyn Display> = AnyShape::from(Circle(2.0)).into();
+    assert_eq!(boxed.to_string(), "circle 2");
+
+    let mut numbers = Numbers::from(0..3);
+    assert_eq!(numbers.as_dyn_iterator_mut().next(), Some(0));
+    assert_eq!(numbers.as_dyn_double_ended_iterator_mut().next_back(), Some(2));
+    let rest: Box<dyn Iterator<Item = u32>> = numbers.into();
+    assert_eq!(rest.collect::<Vec<_>>(), vec![1]);
+
+    let word = String::from("word");
+    let texts = texts(&word, "cow");
+    assert_eq!(texts.iter().map(ToString::to_string).collect::<Vec<_>>(), ["word", "cow"]);
+}
@@ -0,0 +1,113 @@
+nodyn::codec!();
+
+#[derive(Debug, PartialEq)]
+pub struct Point {
+    x: i16,
+    y: i16,
+}
+
+impl NodynEncode for Point {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.x.encode(out);
+        self.y.encode(out);
+    }
+
+    fn decode(input: &[u8]) -> Result<(Self, usize), NodynDecodeError> {
+        let (x, read) = i16::decode(input)?;
+        let (y, len) = i16::decode(&input[read..])?;
+        Ok((Self { x, y }, read + len))
+    }
+}
+
+nodyn::nodyn! {
+    #[derive(Debug, PartialEq)]
+    pub enum Event {
+        u64,
+        i32,
+        f32,
+        bool,
+        char,
+        String,
+        Option<u8>,
+        Vec<Point>,
+    }
+    vec Events;
+    impl codec;
+}
+
+nodyn::nodyn! {
+    #[derive(Debug, PartialEq)]
+    pub enum Tagged {
+        #[tag = 300]
+        u8,
+        #[tag = 7]
+        Point,
+    }
+    impl codec;
+}
+
+fn round_trip(event: Event) {
+    let mut out = Vec::new();
+    event.encode(&mut out);
+    out.push(0xff);
+    let (decoded, read) = Event::decode(&out).unwrap();
+    assert_eq!(decoded, event);
+    assert_eq!(read, out.len() - 1);
+}
+
+fn main() {
+    round_trip(Event::from(u64::MAX));
+    round_trip(Event::from(-12345));
+    round_trip(Event::from(1.5f32));
+    round_trip(Event::from(true));
+    round_trip(Event::from('ü'));
+    round_trip(Event::from("hello".to_string()));
+    round_trip(Event::from(Some(3u8)));
+    round_trip(Event::from(None::<u8>));
+    round_trip(Event::from(vec![Point { x: -1, y: 2 }]));
+
+    let mut out = Vec::new();
+    Event::from(300u64).encode(&mut out);
+    assert_eq!(out, [0, 0xac, 0x02]);
+    out.clear();
+    Event::from(-1).encode(&mut out);
+    assert_eq!(out, [1, 1]);
+
+    assert_eq!(Event::decode(&[]), Err(NodynDecodeError::UnexpectedEnd));
+    assert_eq!(Event::decode(&[9]), Err(NodynDecodeError::UnknownTag(9)));
+    assert_eq!(Event::decode(&[3, 2]), Err(NodynDecodeError::InvalidData));
+    assert_eq!(Event::decode(&[5, 3, b'a']), Err(NodynDecodeError::UnexpectedEnd));
+
+    let mut out = Vec::new();
+    Tagged::from(Point { x: 1, y: -1 }).encode(&mut out);
+    assert_eq!(out, [7, 2, 1]);
+    out.clear();
+    Tagged::from(4u8).encode(&mut out);
+    assert_eq!(out, [0xac, 0x02, 4]);
+    assert_eq!(Tagged::decode(&out), Ok((Tagged::U8(4), 3)));
+
+    let events = events![1u64, "log".to_string(), false];
+    let mut out = Vec::new();
+    events.encode(&mut out);
+    assert_eq!(out[0], 3);
+    let (decoded, read) = Events::decode(&out).unwrap();
+    assert_eq!(decoded, events);
+    assert_eq!(read, out.len());
+    assert_eq!(Events::decode(&out[..4]), Err(NodynDecodeError::UnexpectedEnd));
+
+    let mut out = Vec::new();
+    u128::MAX.encode(&mut out);
+    assert_eq!(out.len(), 19);
+    assert_eq!(out[18], 0x03);
+    assert_eq!(u128::decode(&out), Ok((u128::MAX, 19)));
+    out[18] = 0x04;
+    assert_eq!(u128::decode(&out), Err(NodynDecodeError::InvalidData));
+
+    // a huge count of zero sized items is rejected instead of looping
+    let mut out = Vec::new();
+    usize::MAX.encode(&mut out);
+    assert_eq!(Vec::<()>::decode(&out), Err(NodynDecodeError::InvalidData));
+    let mut out = Vec::new();
+    vec![()].encode(&mut out);
+    assert_eq!(Vec::<()>::decode(&out), Ok((vec![()], 1)));
+}
@@ -0,0 +1,47 @@
+nodyn::nodyn! {
+    #[derive(Debug, PartialEq)]
+    pub enum Value {
+        i32,
+        String,
+    }
+    impl combinators;
+}
+
+nodyn::nodyn! {
+    #[derive(Debug, PartialEq)]
+    pub enum Single {
+        u8,
+    }
+    impl combinators;
+}
+
+fn main() {
+    assert_eq!(Value::from(1).unwrap_i32(), 1);
+    assert_eq!(Value::from("a".to_string()).expect_string("a string"), "a");
+    assert_eq!(Value::from(2).map_i32(|n| n * 2), Value::I32(4));
+    assert_eq!(Value::from(2).map_string(|s| s + "!"), Value::I32(2));
+    assert_eq!(
+        Value::from(3).and_then_i32(|n| Value::String(n.to_string())),
+        Value::String("3".to_string())
+    );
+    assert_eq!(Value::from("b".to_string()).into_i32_or(0), 0);
+    assert_eq!(Value::from(5).into_i32_or(0), 5);
+
+    let mut value = Value::from(6);
+    assert_eq!(value.replace_string("c".to_string()), Value::I32(6));
+    assert_eq!(value, Value::String("c".to_string()));
+
+    std::panic::set_hook(Box::new(|_| {}));
+    let panic = std::panic::catch_unwind(|| Value::from(7).unwrap_string()).unwrap_err();
+    assert_eq!(
+        panic.downcast_ref::<String>().map(String::as_str),
+        Some("called `Value::unwrap_string()` on a `i32` value")
+    );
+    let panic = std::panic::catch_unwind(|| Value::from(7).expect_string("no text")).unwrap_err();
+    assert_eq!(
+        panic.downcast_ref::<String>().map(String::as_str),
+        Some("no text: found a `i32` value")
+    );
+
+    assert_eq!(Single::from(8).map_u8(|n| n + 1).unwrap_u8(), 9);
+}
@@ -0,0 +1,34 @@
+use nodyn::nodyn;
+
+nodyn! {
+    #[derive(Debug, Clone)]
+    pub enum Value {
+        #[into(i64, f64)]
+        i32,
+        i64,
+        f64,
+        String,
+    }
+    impl compare;
+    vec;
+}
+
+fn main() {
+    assert!(Value::I32(3) > Value::F64(1.0));
+    assert!(Value::F64(1.0) < Value::I32(3));
+    assert!(Value::I32(3) < Value::I64(4));
+    assert_eq!(Value::I32(42), Value::F64(42.0));
+    assert_eq!(Value::I64(42), Value::I32(42));
+    assert_ne!(Value::I32(42), Value::String("42".to_string()));
+    assert_eq!(Value::I64(1).partial_cmp(&Value::F64(1.0)), None);
+    assert_eq!(
+        Value::I32(1).partial_cmp(&Value::String("a".to_string())),
+        None
+    );
+    assert!(Value::String("a".to_string()) < Value::String("b".to_string()));
+
+    let sorted = value_vec![1, 2i64, 3, 3.5];
+    assert!(sorted.is_sorted());
+    let unsorted = value_vec![1, 0.5];
+    assert!(!unsorted.is_sorted());
+}
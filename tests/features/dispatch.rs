@@ -0,0 +1,29 @@
+nodyn::nodyn! {
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum Value {
+        i32,
+        f64,
+        String,
+    }
+    impl dispatch;
+}
+
+fn main() {
+    let values = vec![Value::from(1), Value::from(2.5), Value::from("three".to_string())];
+    let strings = values
+        .iter()
+        .map(|value| value_dispatch!(value, |x| x.to_string()))
+        .collect::<Vec<_>>();
+    assert_eq!(strings, vec!["1", "2.5", "three"]);
+
+    let mut value = Value::from(1);
+    assert_eq!(value_dispatch!(ref value, |x| format!("{x:?}")), "1");
+    value_dispatch!(mut value, |x| *x = Default::default());
+    assert_eq!(value, Value::I32(0));
+
+    let doubled = value_dispatch!(wrap Value::from("ab".to_string()), |x| x.clone() + &x);
+    assert_eq!(doubled, Value::String("abab".to_string()));
+
+    let size = value_dispatch!(value, |x| std::mem::size_of_val(&x));
+    assert_eq!(size, 4);
+}
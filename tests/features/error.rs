@@ -0,0 +1,79 @@
+use std::error::Error;
+use std::fmt;
+use std::num::ParseIntError;
+
+use nodyn::nodyn;
+
+#[derive(Debug)]
+pub struct ConfigError {
+    source: ParseIntError,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid config")
+    }
+}
+
+impl Error for ConfigError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+nodyn! {
+    #[derive(Debug)]
+    pub enum AppError {
+        #[context("reading input")]
+        std::io::Error,
+        ParseIntError,
+        ConfigError,
+    }
+    impl Error;
+}
+
+nodyn! {
+    #[derive(Debug)]
+    pub enum WrappedError {
+        ParseIntError,
+        ConfigError,
+    }
+    impl Error(source);
+}
+
+fn parse(s: &str) -> Result<i32, AppError> {
+    Ok(s.parse::<i32>()?)
+}
+
+fn config() -> ConfigError {
+    ConfigError {
+        source: "x".parse::<i32>().unwrap_err(),
+    }
+}
+
+fn main() {
+    assert_eq!(parse("12").unwrap(), 12);
+    let error = parse("twelve").unwrap_err();
+    assert_eq!(error.to_string(), "invalid digit found in string");
+    assert!(error.source().is_none());
+
+    let io = AppError::from(std::io::Error::other("disk on fire"));
+    assert_eq!(io.to_string(), "reading input: disk on fire");
+
+    // `impl Error` forwards the source of the wrapped error
+    let error = AppError::from(config());
+    assert_eq!(error.to_string(), "invalid config");
+    assert_eq!(
+        error.source().unwrap().to_string(),
+        "invalid digit found in string"
+    );
+
+    // `impl Error(source)` makes the wrapped error the source
+    let error = WrappedError::from(config());
+    assert_eq!(error.source().unwrap().to_string(), "invalid config");
+    let error = WrappedError::from("".parse::<i32>().unwrap_err());
+    assert!(error.source().unwrap().is::<ParseIntError>());
+
+    let boxed: Box<dyn Error> = Box::new(AppError::from(config()));
+    assert_eq!(boxed.to_string(), "invalid config");
+}
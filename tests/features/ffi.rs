@@ -0,0 +1,100 @@
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point {
+    x: f32,
+    y: f32,
+}
+
+nodyn::nodyn! {
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Value {
+        i32,
+        f64,
+        bool,
+        Point,
+        Bytes([u8; 4]),
+    }
+    impl ffi;
+}
+
+nodyn::nodyn! {
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Tagged {
+        #[tag = 10]
+        u8,
+        #[tag = 20]
+        Type(u16),
+        #[tag = 30]
+        Crate(i8),
+    }
+    impl ffi;
+}
+
+extern "C" fn round_trip(value: ValueFfi) -> ValueFfi {
+    value
+}
+
+/// Stands in for C code writing a tag, as the layout is the tag followed by the value.
+fn unknown_tag(mut ffi: TaggedFfi) -> TaggedFfi {
+    // SAFETY: `TaggedFfi` is `#[repr(C)]` and starts with its `u32` tag
+    unsafe { *(&raw mut ffi).cast::<u32>() = 11 };
+    ffi
+}
+
+fn main() {
+    for value in [
+        Value::from(-3),
+        Value::from(2.5),
+        Value::from(true),
+        Value::from(Point { x: 1.0, y: -1.0 }),
+        Value::from([1, 2, 3, 4]),
+    ] {
+        let ffi = round_trip(value.to_ffi());
+        assert_eq!(ffi.kind(), Some(value.kind()));
+        assert_eq!(ffi.tag(), value.tag());
+        // SAFETY: `ffi` was created by `to_ffi`
+        assert_eq!(unsafe { Value::from_ffi(ffi) }, Some(value));
+        // SAFETY: `ffi` was created by `to_ffi`
+        assert_eq!(unsafe { Value::from_ffi(ValueFfi::from(value)) }, Some(value));
+    }
+    assert_eq!(Value::from(true).tag(), 2);
+    assert_eq!(Value::TAGS, [0, 1, 2, 3, 4]);
+    assert_eq!(
+        ValueFfi::C_HEADER,
+        "/* Tags of `Value`. */\n\
+         #define VALUE_I32 0u\n\
+         #define VALUE_F64 1u\n\
+         #define VALUE_BOOL 2u\n\
+         #define VALUE_POINT 3u\n\
+         #define VALUE_BYTES 4u\n"
+    );
+    assert_eq!(
+        std::mem::size_of::<ValueFfi>(),
+        std::mem::size_of::<u64>() * 2
+    );
+
+    let ffi = Tagged::from(7u16).to_ffi();
+    assert_eq!(ffi.kind(), Some(TaggedKind::Type));
+    assert_eq!(ffi.tag(), 20);
+    // SAFETY: `ffi` was created by `to_ffi`
+    assert_eq!(unsafe { Tagged::from_ffi(ffi) }, Some(Tagged::Type(7)));
+    // SAFETY: `ffi` was created by `to_ffi`
+    assert_eq!(
+        unsafe { Tagged::from_ffi(Tagged::Crate(-1).to_ffi()) },
+        Some(Tagged::Crate(-1))
+    );
+
+    // a tag set by C that isn't one of the enum
+    let unknown = unknown_tag(Tagged::from(1u8).to_ffi());
+    assert_eq!(unknown.tag(), 11);
+    assert_eq!(unknown.kind(), None);
+    // SAFETY: the tag selects no variant, so the value isn't read
+    assert_eq!(unsafe { Tagged::from_ffi(unknown) }, None);
+    assert_eq!(
+        TaggedFfi::C_HEADER,
+        "/* Tags of `Tagged`. */\n\
+         #define TAGGED_U8 10u\n\
+         #define TAGGED_TYPE 20u\n\
+         #define TAGGED_CRATE 30u\n"
+    );
+}
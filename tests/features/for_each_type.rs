@@ -0,0 +1,62 @@
+mod values {
+    nodyn::nodyn! {
+        #[derive(Debug)]
+        pub enum Value {
+            i32,
+            String,
+            Vec<u8>,
+        }
+        impl for_each_type;
+    }
+}
+
+macro_rules! type_name {
+    ($variant:ident, $ty:ty) => {
+        NAMES.with_borrow_mut(|names| {
+            names.push(format!("{}: {}", stringify!($variant), std::any::type_name::<$ty>()))
+        });
+    };
+}
+
+macro_rules! is_default {
+    ($enum:path, $variant:ident, $ty:ty) => {
+        const _: fn($ty) -> $enum = <$enum as From<$ty>>::from;
+
+        impl IsDefault for $ty {
+            fn is_default(&self) -> bool {
+                *self == <$ty>::default()
+            }
+        }
+    };
+}
+
+use values::Value;
+
+trait IsDefault {
+    fn is_default(&self) -> bool;
+}
+
+value_for_each_type!(is_default, enum);
+
+thread_local! {
+    static NAMES: std::cell::RefCell<Vec<String>> = const { std::cell::RefCell::new(Vec::new()) };
+}
+
+fn main() {
+    value_for_each_type!(type_name);
+    NAMES.with_borrow(|names| {
+        assert_eq!(
+            names,
+            &[
+                "I32: i32",
+                "String: alloc::string::String",
+                "VecU8: alloc::vec::Vec<u8>"
+            ]
+        );
+    });
+    assert!(0.is_default());
+    assert!(!"text".to_string().is_default());
+    assert!(Vec::<u8>::new().is_default());
+    let value: Value = 1.into();
+    assert!(matches!(value, Value::I32(1)));
+}
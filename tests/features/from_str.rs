@@ -0,0 +1,43 @@
+use nodyn::nodyn;
+
+nodyn! {
+    #[derive(Debug, PartialEq)]
+    pub enum Value {
+        i32,
+        bool,
+        String,
+    }
+    impl FromStr;
+}
+
+nodyn! {
+    #[derive(Debug, PartialEq)]
+    pub enum Number {
+        i32,
+        #[parse(priority = 1)]
+        u8,
+        #[parse(skip)]
+        f64,
+    }
+    impl FromStr;
+}
+
+fn main() {
+    assert_eq!("42".parse::<Value>(), Ok(Value::I32(42)));
+    assert_eq!("true".parse::<Value>(), Ok(Value::Bool(true)));
+    assert_eq!(
+        "hello".parse::<Value>(),
+        Ok(Value::String("hello".to_string()))
+    );
+
+    assert_eq!("42".parse::<Number>(), Ok(Number::U8(42)));
+    assert_eq!("-42".parse::<Number>(), Ok(Number::I32(-42)));
+    let error = "4.2".parse::<Number>().unwrap_err();
+    assert_eq!(error.errors().len(), 2);
+    assert_eq!(error.errors()[0].0, "u8");
+    assert_eq!(error.errors()[1].0, "i32");
+    assert_eq!(
+        error.to_string(),
+        "no variant of `Number` could be parsed: u8: invalid digit found in string; i32: invalid digit found in string"
+    );
+}
@@ -0,0 +1,102 @@
+use std::future::{Future, Ready, ready};
+use std::pin::Pin;
+use std::task::{Context, Poll, Waker};
+
+use nodyn::nodyn;
+
+type BoxFuture = Pin<Box<dyn Future<Output = u32>>>;
+
+nodyn! {
+    pub enum Fetch {
+        Ready<u32>,
+        BoxFuture,
+    }
+    impl Future<Output = u32>;
+}
+
+pub trait Stream {
+    type Item;
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>>;
+    fn size_hint(self: Pin<&Self>) -> usize;
+}
+
+pub struct Countdown(u32);
+
+impl Stream for Countdown {
+    type Item = u32;
+    fn poll_next(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<u32>> {
+        if self.0 == 0 {
+            Poll::Ready(None)
+        } else {
+            self.0 -= 1;
+            Poll::Ready(Some(self.0))
+        }
+    }
+
+    fn size_hint(self: Pin<&Self>) -> usize {
+        self.0 as usize
+    }
+}
+
+pub struct Empty;
+
+impl Stream for Empty {
+    type Item = u32;
+    fn poll_next(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<u32>> {
+        Poll::Ready(None)
+    }
+
+    fn size_hint(self: Pin<&Self>) -> usize {
+        0
+    }
+}
+
+nodyn! {
+    pub enum Numbers {
+        Countdown,
+        Empty,
+    }
+    impl Stream {
+        type Item = u32;
+        fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<u32>>;
+        fn size_hint(self: Pin<&Self>) -> usize;
+    }
+}
+
+#[nodyn::returns]
+fn fetch(cached: bool) -> impl Future<Output = u32> {
+    if cached {
+        marker!(ready(1))
+    } else {
+        // `async` blocks are not `Unpin`
+        marker!(Box::pin(async { 2 }))
+    }
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = std::pin::pin!(future);
+    let mut cx = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
+
+fn main() {
+    assert_eq!(block_on(Fetch::from(ready(7))), 7);
+    let boxed: BoxFuture = Box::pin(async { 8 });
+    assert_eq!(block_on(Fetch::from(boxed)), 8);
+
+    let mut cx = Context::from_waker(Waker::noop());
+    let mut numbers = Numbers::from(Countdown(2));
+    assert_eq!(Pin::new(&numbers).size_hint(), 2);
+    assert_eq!(Pin::new(&mut numbers).poll_next(&mut cx), Poll::Ready(Some(1)));
+    assert_eq!(Pin::new(&mut numbers).poll_next(&mut cx), Poll::Ready(Some(0)));
+    assert_eq!(Pin::new(&mut numbers).poll_next(&mut cx), Poll::Ready(None));
+    let mut empty = Numbers::from(Empty);
+    assert_eq!(Pin::new(&mut empty).poll_next(&mut cx), Poll::Ready(None));
+
+    assert_eq!(block_on(fetch(true)), 1);
+    assert_eq!(block_on(fetch(false)), 2);
+}
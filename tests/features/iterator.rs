@@ -0,0 +1,50 @@
+use std::iter::{Chain, FusedIterator, Rev};
+use std::ops::Range;
+use std::vec::IntoIter;
+
+use nodyn::nodyn;
+
+nodyn! {
+    pub enum Numbers {
+        Range<u32>,
+        Rev<Range<u32>>,
+        IntoIter<u32>,
+    }
+    impl Iterator<Item = u32> + DoubleEndedIterator + ExactSizeIterator + FusedIterator;
+}
+
+nodyn! {
+    pub enum Chained {
+        Range<u32>,
+        Chain<Range<u32>, Range<u32>>,
+    }
+    impl Iterator<Item = u32>;
+}
+
+fn numbers(kind: u8) -> Numbers {
+    match kind {
+        0 => (0..3).into(),
+        1 => (0..3).rev().into(),
+        _ => vec![7, 8].into_iter().into(),
+    }
+}
+
+fn assert_fused<I: FusedIterator>(_: &I) {}
+
+fn main() {
+    assert_eq!(numbers(0).collect::<Vec<_>>(), vec![0, 1, 2]);
+    assert_eq!(numbers(1).collect::<Vec<_>>(), vec![2, 1, 0]);
+    assert_eq!(numbers(2).rev().collect::<Vec<_>>(), vec![8, 7]);
+    assert_eq!(numbers(0).len(), 3);
+    assert_eq!(numbers(2).size_hint(), (2, Some(2)));
+    assert_eq!(numbers(1).sum::<u32>(), 3);
+    assert_fused(&numbers(0));
+
+    let mut numbers = numbers(0);
+    assert_eq!(numbers.next_back(), Some(2));
+    assert_eq!(numbers.next(), Some(0));
+    assert_eq!(numbers.len(), 1);
+
+    let chained = Chained::from((0..2).chain(5..6));
+    assert_eq!(chained.collect::<Vec<_>>(), vec![0, 1, 5]);
+}
@@ -0,0 +1,52 @@
+nodyn::nodyn! {
+    #[derive(Debug)]
+    pub enum Value<'a> {
+        i32,
+        &'a str,
+        Vec<u8>,
+        [u8; 2],
+    }
+    impl match_type;
+}
+
+fn describe(value: &Value) -> String {
+    value_match!(value,
+        i32(n) if *n < 0 => format!("negative {n}"),
+        i32(n) => format!("number {n}"),
+        &str(s) => format!("text {s}"),
+        Vec<u8>(bytes) => format!("{} bytes", bytes.len()),
+        [u8; 2]([a, b]) => format!("pair {a} {b}"),
+    )
+}
+
+fn main() {
+    assert_eq!(describe(&Value::from(-1)), "negative -1");
+    assert_eq!(describe(&Value::from(1)), "number 1");
+    assert_eq!(describe(&Value::from("hi")), "text hi");
+    assert_eq!(describe(&Value::from(vec![1u8, 2, 3])), "3 bytes");
+    assert_eq!(describe(&Value::from([4u8, 5])), "pair 4 5");
+
+    let value = Value::from(7);
+    let doubled = value_match!(value, i32(n) => n * 2, _ => 0);
+    assert_eq!(doubled, 14);
+
+    let value = Value::from("seven");
+    let is_number = value_match!(&value, i32(_) => true, other => {
+        assert!(matches!(other, Value::StrRef(_)));
+        false
+    });
+    assert!(!is_number);
+
+    // block bodies don't need a comma
+    let value = Value::from(vec![1u8, 2]);
+    let len = value_match!(value,
+        i32(_) => { 0 }
+        Vec<u8>(bytes) if bytes.is_empty() => { 0 },
+        Vec<u8>(bytes) => {
+            let len = bytes.len();
+            len * 2
+        }
+        _ => 1,
+    );
+    assert_eq!(len, 4);
+}
@@ -0,0 +1,76 @@
+use nodyn::nodyn;
+
+nodyn! {
+    #[derive(Debug, PartialEq)]
+    pub enum Value {
+        i32,
+        String,
+    }
+
+    vec;
+    impl is_as;
+}
+
+nodyn! {
+    #[derive(Debug)]
+    pub enum Borrowed<'a, T> {
+        &'a str,
+        Vec<T>,
+    }
+    impl is_as;
+}
+
+fn describe<T: ValueMember + std::fmt::Debug>(value: &Value) -> String {
+    match value.get::<T>() {
+        Some(inner) => format!("{inner:?}"),
+        None => "other".to_string(),
+    }
+}
+
+fn store<T: ValueMember>(values: &mut Vec<Value>, value: T) -> usize {
+    values.push(value.wrap());
+    T::INDEX
+}
+
+fn main() {
+    let mut stored = Vec::new();
+    assert_eq!(store(&mut stored, 7), 0);
+    assert_eq!(store(&mut stored, "seven".to_string()), 1);
+    assert_eq!(i32::unwrap_from(stored.remove(0)), Ok(7));
+    assert_eq!(
+        i32::unwrap_from(stored.remove(0)),
+        Err(Value::String("seven".to_string()))
+    );
+    assert_eq!(<Vec<u8> as BorrowedMember<u8>>::INDEX, 1);
+
+    let mut value: Value = 42.into();
+    assert!(value.is::<i32>());
+    assert!(!value.is::<String>());
+    assert_eq!(value.get::<i32>(), Some(&42));
+    assert_eq!(value.get::<String>(), None);
+    if let Some(number) = value.get_mut::<i32>() {
+        *number += 1;
+    }
+    assert_eq!(describe::<i32>(&value), "43");
+    assert_eq!(describe::<String>(&value), "other");
+
+    let value = value.take::<String>().unwrap_err();
+    assert_eq!(value.take::<i32>(), Ok(43));
+
+    let borrowed: Borrowed<u8> = "hello".into();
+    assert!(borrowed.is::<&str>());
+    assert_eq!(borrowed.get::<Vec<u8>>(), None);
+    assert_eq!(borrowed.take::<&str>().ok(), Some("hello"));
+
+    let mut values = value_vec![1, "two".to_string(), 3];
+    assert_eq!(values.count_of::<i32>(), 2);
+    assert_eq!(values.first_of::<String>().map(String::as_str), Some("two"));
+    for number in values.iter_of_mut::<i32>() {
+        *number *= 10;
+    }
+    assert_eq!(values.iter_of::<i32>().copied().collect::<Vec<_>>(), vec![10, 30]);
+    if let Some(text) = values.first_of_mut::<String>() {
+        text.push('!');
+    }
+    assert_eq!(values.first_of::<String>().map(String::as_str), Some("two!"));
+}
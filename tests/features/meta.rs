@@ -0,0 +1,46 @@
+nodyn::nodyn! {
+    #[derive(Debug, Clone)]
+    pub enum Reading {
+        #[meta(label = "Integer", unit = "count")]
+        i64,
+        #[meta(unit = "meters", label = "Distance")]
+        f64,
+        #[meta(label = "Text", unit = "")]
+        String,
+    }
+}
+
+nodyn::nodyn! {
+    pub enum Tagged {
+        #[tag = 5]
+        #[meta(icon = "#")]
+        u8,
+        #[tag = 1]
+        #[meta(icon = "*")]
+        u16,
+    }
+}
+
+const DISTANCE_UNIT: &str = Reading::F64(0.0).unit();
+
+fn main() {
+    let reading = Reading::from(3i64);
+    assert_eq!(reading.label(), "Integer");
+    assert_eq!(reading.unit(), "count");
+    assert_eq!(Reading::from("a".to_string()).label(), "Text");
+    assert_eq!(DISTANCE_UNIT, "meters");
+
+    assert_eq!(ReadingKind::F64.label(), "Distance");
+    assert_eq!(Reading::META_KEYS, ["label", "unit"]);
+    assert_eq!(
+        Reading::META,
+        [
+            (ReadingKind::I64, ["Integer", "count"]),
+            (ReadingKind::F64, ["Distance", "meters"]),
+            (ReadingKind::String, ["Text", ""]),
+        ]
+    );
+
+    assert_eq!(Tagged::from(1u16).icon(), "*");
+    assert_eq!(TaggedKind::from_tag(5).map(TaggedKind::icon), Some("#"));
+}
@@ -0,0 +1,87 @@
+#[derive(Debug, Clone, PartialEq)]
+pub struct HTTPResponse(u16);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct JSONBody(String);
+
+nodyn::nodyn! {
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum Message {
+        HTTPResponse,
+        #[nodyn(snake = "body")]
+        JSONBody,
+    }
+    vec;
+    impl is_as combinators;
+}
+
+nodyn::nodyn! {
+    #[derive(Debug, Clone, PartialEq)]
+    #[nodyn(is = "holds_{}", accessor = "as_{}", vec = "{}List")]
+    pub enum Value {
+        i32,
+        String,
+    }
+    vec;
+    impl is_as;
+}
+
+nodyn::nodyn! {
+    #[derive(Debug, Clone, PartialEq)]
+    #[nodyn(first = "head_{}", iter = "{}_values", count = "num_{}", contains = "has_{}")]
+    #[nodyn(unwrap = "take_{}", into_or = "{}_or_else", replace = "set_{}")]
+    pub enum Item {
+        i32,
+        String,
+    }
+    vec;
+    impl combinators;
+}
+
+nodyn::nodyn! {
+    #[derive(Debug, Clone, PartialEq)]
+    #[nodyn(case = "snake")]
+    pub enum Legacy {
+        HTTPResponse,
+    }
+    impl is_as;
+}
+
+fn main() {
+    let message = Message::from(HTTPResponse(200));
+    assert!(message.is_http_response());
+    assert_eq!(message.try_as_http_response_ref(), Some(&HTTPResponse(200)));
+    let body = Message::from(JSONBody("{}".to_string()));
+    assert!(body.is_body());
+    assert_eq!(body.clone().unwrap_body(), JSONBody("{}".to_string()));
+
+    let messages = message_vec![message, body];
+    assert_eq!(messages.count_http_response(), 1);
+    assert_eq!(messages.first_body(), Some(&JSONBody("{}".to_string())));
+
+    let value = Value::from(3);
+    assert!(value.holds_i32());
+    assert!(!value.holds_string());
+    assert_eq!(value.as_i32_ref(), Some(&3));
+    assert_eq!(value.clone().as_i32(), Some(3));
+    let values: ValueList = value_list![1, "a".to_string()];
+    assert_eq!(values.count_string(), 1);
+
+    assert!(Legacy::from(HTTPResponse(404)).is_h_t_t_p_response());
+
+    let mut items = item_vec![1, "a".to_string(), 2];
+    assert_eq!(items.head_i32(), Some(&1));
+    assert_eq!(items.head_i32_mut(), Some(&mut 1));
+    assert_eq!(items.i32_values().sum::<i32>(), 3);
+    assert_eq!(items.i32_mut_values().count(), 2);
+    assert_eq!(items.num_string(), 1);
+    assert!(items.has_string(&"a".to_string()));
+    // unconfigured methods keep their names
+    assert_eq!(items.last_i32(), Some(&2));
+
+    let mut item = Item::from(5);
+    assert_eq!(item.clone().take_i32(), 5);
+    assert_eq!(item.clone().string_or_else("none".to_string()), "none");
+    assert_eq!(item.set_string("b".to_string()), Item::I32(5));
+    assert_eq!(item.expect_string("a string"), "b");
+}
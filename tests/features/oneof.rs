@@ -0,0 +1,69 @@
+nodyn::nodyn! {
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum Value<'a> {
+        i32,
+        String,
+        &'a str,
+        Box(Box<u8>),
+        Crate(u16),
+        #[nodyn(snake = "self")]
+        f32,
+    }
+    impl oneof;
+}
+
+fn main() {
+    let oneof = ValueOneOf::from(Value::from("text".to_string()));
+    assert_eq!(
+        oneof,
+        ValueOneOf {
+            string: Some("text".to_string()),
+            ..ValueOneOf::default()
+        }
+    );
+    assert_eq!(Value::try_from(oneof), Ok(Value::String("text".to_string())));
+
+    let oneof = ValueOneOf::from(Value::from("str"));
+    assert_eq!(oneof.str_ref, Some("str"));
+    assert_eq!(oneof.i32, None);
+    assert_eq!(Value::try_from(oneof), Ok(Value::StrRef("str")));
+
+    let oneof = ValueOneOf {
+        r#box: Some(Box::new(1)),
+        ..ValueOneOf::default()
+    };
+    assert_eq!(Value::try_from(oneof), Ok(Value::Box(Box::new(1))));
+
+    // `crate`, `self`, `super` and `Self` can't be raw identifiers
+    let oneof = ValueOneOf::from(Value::Crate(2));
+    assert_eq!(oneof.crate_, Some(2));
+    let oneof = ValueOneOf::from(Value::from(1.5f32));
+    assert_eq!(oneof.self_, Some(1.5));
+    let error = Value::try_from(ValueOneOf {
+        crate_: Some(1),
+        self_: Some(1.0),
+        ..ValueOneOf::default()
+    })
+    .unwrap_err();
+    assert_eq!(error, ValueOneOfError::Multiple(vec!["crate_", "self_"]));
+
+    assert_eq!(
+        Value::try_from(ValueOneOf::default()),
+        Err(ValueOneOfError::Empty)
+    );
+    let error = Value::try_from(ValueOneOf {
+        i32: Some(1),
+        str_ref: Some("a"),
+        ..ValueOneOf::default()
+    })
+    .unwrap_err();
+    assert_eq!(error, ValueOneOfError::Multiple(vec!["i32", "str_ref"]));
+    assert_eq!(
+        error.to_string(),
+        "only one field of `ValueOneOf` can be set, found `i32`, `str_ref`"
+    );
+    assert_eq!(
+        ValueOneOfError::Empty.to_string(),
+        "no field of `ValueOneOf` is set"
+    );
+}
@@ -0,0 +1,40 @@
+use nodyn::nodyn;
+use std::fmt;
+
+nodyn! {
+    #[derive(Debug, PartialEq)]
+    pub enum Value {
+        i32,
+        bool,
+        String,
+        #[parse(skip)]
+        f64,
+    }
+    impl FromStr introspection;
+    impl fmt::Display {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+    }
+}
+
+fn main() {
+    assert_eq!(Value::parse_as("String", "42"), Ok(Value::String("42".to_string())));
+    assert_eq!(Value::parse_as("i32", "42"), Ok(Value::I32(42)));
+
+    for value in [Value::I32(7), Value::Bool(false), Value::String("true".to_string())] {
+        let parsed = Value::parse_as(value.type_name(), &value.to_string()).unwrap();
+        assert_eq!(parsed, value);
+    }
+
+    let error = Value::parse_as("bool", "yes").unwrap_err();
+    assert_eq!(error.errors()[0].0, "bool");
+    assert_eq!(error.unknown_type(), None);
+
+    let error = Value::parse_as("u8", "42").unwrap_err();
+    assert_eq!(error.unknown_type(), Some("u8"));
+    assert_eq!(error.to_string(), "`u8` is not a type of `Value`");
+
+    let error = Value::parse_as("f64", "1.5").unwrap_err();
+    assert_eq!(error.unknown_type(), None);
+    assert_eq!(error.skipped_type(), Some("f64"));
+    assert_eq!(error.to_string(), "`f64` is skipped when parsing `Value`");
+}
@@ -0,0 +1,58 @@
+use std::fmt::{self, Display};
+
+use nodyn::returns;
+
+struct Celsius(f64);
+
+impl Display for Celsius {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}°C", self.0)
+    }
+}
+
+#[returns]
+fn describe(n: i32) -> impl Display {
+    if n < 0 {
+        return marker!(Celsius(f64::from(n)));
+    }
+    match n {
+        0 => marker!("zero"),
+        1 => marker!(1u8),
+        _ => marker!(format!("many ({n})")),
+    }
+}
+
+#[returns(impl Iterator<Item = u8> + DoubleEndedIterator)]
+fn digits(reverse: bool, upto: u8) -> impl DoubleEndedIterator<Item = u8> {
+    if reverse {
+        marker!((0..upto).rev())
+    } else {
+        marker!(vec![1, 2, 3].into_iter().take(upto as usize))
+    }
+}
+
+#[returns]
+fn both(debug: bool) -> impl Display + fmt::Debug {
+    // closures keep their own `marker!`-free returns
+    let double = |x: i32| x * 2;
+    if debug {
+        marker!(double(21))
+    } else {
+        marker!("text")
+    }
+}
+
+fn main() {
+    assert_eq!(describe(-3).to_string(), "-3°C");
+    assert_eq!(describe(0).to_string(), "zero");
+    assert_eq!(describe(1).to_string(), "1");
+    assert_eq!(describe(7).to_string(), "many (7)");
+
+    assert_eq!(digits(true, 3).collect::<Vec<_>>(), vec![2, 1, 0]);
+    assert_eq!(digits(false, 2).collect::<Vec<_>>(), vec![1, 2]);
+    assert_eq!(digits(false, 3).next_back(), Some(3));
+
+    assert_eq!(format!("{:?}", both(true)), "42");
+    assert_eq!(format!("{:?}", both(false)), "\"text\"");
+    assert_eq!(both(false).to_string(), "text");
+}
@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+
+nodyn::nodyn! {
+    #[derive(Debug, PartialEq)]
+    pub enum Tagged {
+        i32,
+        #[serde(rename = "text")]
+        String,
+        Vec<u8>,
+    }
+    impl serde;
+}
+
+nodyn::nodyn! {
+    #[derive(Debug, PartialEq)]
+    pub enum Untagged<'a> {
+        bool,
+        &'a str,
+    }
+    impl serde(untagged);
+}
+
+nodyn::nodyn! {
+    #[derive(Debug, PartialEq)]
+    pub enum Adjacent<T> {
+        #[tag = 1]
+        u8,
+        #[tag = 2]
+        Vec<T>,
+    }
+    impl serde(adjacent);
+}
+
+nodyn::nodyn! {
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum Item {
+        i32,
+        String,
+    }
+    vec Items;
+    impl serde(untagged);
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Record<'a> {
+    #[serde(borrow)]
+    values: Vec<Untagged<'a>>,
+    items: Items,
+}
+
+fn main() {
+    let json = serde_json::to_string(&Tagged::from("a".to_string())).unwrap();
+    assert_eq!(json, r#"{"text":"a"}"#);
+    let json = serde_json::to_string(&Tagged::from(vec![1u8])).unwrap();
+    assert_eq!(json, r#"{"Vec<u8>":[1]}"#);
+    let value: Tagged = serde_json::from_str(r#"{"i32":3}"#).unwrap();
+    assert_eq!(value, Tagged::I32(3));
+    assert!(serde_json::from_str::<Tagged>(r#"{"u8":3}"#).is_err());
+
+    let json = serde_json::to_string(&Adjacent::<i8>::from(vec![-1i8])).unwrap();
+    assert_eq!(json, r#"{"tag":"2","content":[-1]}"#);
+    let value: Adjacent<i8> = serde_json::from_str(r#"{"tag":"1","content":4}"#).unwrap();
+    assert_eq!(value, Adjacent::U8(4));
+
+    let json = r#"{"values":[true,"text"],"items":[1,"two"]}"#;
+    let record: Record = serde_json::from_str(json).unwrap();
+    assert_eq!(record.values, vec![Untagged::Bool(true), Untagged::StrRef("text")]);
+    assert_eq!(record.items.len(), 2);
+    assert_eq!(serde_json::to_string(&record).unwrap(), json);
+}
@@ -0,0 +1,26 @@
+nodyn::nodyn! {
+    #[derive(Debug, PartialEq)]
+    pub enum Value<'a> {
+        #[tag = 7]
+        i32,
+        #[tag = 3]
+        &'a str,
+        #[tag = 100]
+        Vec<u8>,
+    }
+}
+
+fn main() {
+    assert_eq!(Value::TAGS, [7, 3, 100]);
+    assert_eq!(Value::from(1).tag(), 7);
+    assert_eq!(Value::from("a").tag(), 3);
+    assert_eq!(Value::from(vec![1u8]).kind(), ValueKind::VecU8);
+
+    assert_eq!(ValueKind::from_tag(3), Some(ValueKind::StrRef));
+    assert_eq!(ValueKind::from_tag(4), None);
+    assert_eq!(ValueKind::I32.tag(), 7);
+    assert_eq!(ValueKind::VecU8 as u32, 100);
+
+    const TAG: u32 = ValueKind::StrRef.tag();
+    assert_eq!(TAG, 3);
+}
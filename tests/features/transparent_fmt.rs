@@ -0,0 +1,59 @@
+use nodyn::nodyn;
+
+#[derive(Debug)]
+pub struct Point {
+    x: i32,
+    y: i32,
+}
+
+nodyn! {
+    pub enum Value {
+        i32,
+        #[display("{}!")]
+        String,
+        f64,
+    }
+    impl transparent(Debug, Display);
+}
+
+nodyn! {
+    pub enum Shape {
+        Point,
+        i32,
+    }
+    impl transparent(Debug);
+}
+
+nodyn! {
+    pub enum Items<T> {
+        i32,
+        Vec<T>,
+    }
+    impl transparent(Debug);
+}
+
+nodyn! {
+    pub enum Wrapped<T> {
+        i32,
+        Box<T>,
+    }
+    impl transparent(Display);
+}
+
+fn main() {
+    assert_eq!(format!("{:?}", Items::<u8>::from(vec![1, 2])), "[1, 2]");
+    assert_eq!(format!("{}", Wrapped::from(Box::new("boxed"))), "boxed");
+    assert_eq!(format!("{:?}", Value::I32(42)), "42");
+    assert_eq!(format!("{:?}", Value::from("hi".to_string())), "\"hi\"");
+    assert_eq!(format!("{}", Value::I32(42)), "42");
+    assert_eq!(format!("{:>5}", Value::I32(42)), "   42");
+    assert_eq!(format!("{:.2}", Value::F64(3.14159)), "3.14");
+    assert_eq!(format!("{}", Value::from("hi".to_string())), "hi!");
+
+    let point = Shape::from(Point { x: 1, y: 2 });
+    assert_eq!(format!("{point:?}"), "Point { x: 1, y: 2 }");
+    assert_eq!(
+        format!("{point:#?}"),
+        "Point {\n    x: 1,\n    y: 2,\n}"
+    );
+}
@@ -0,0 +1,81 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::hash::{BuildHasher, RandomState};
+
+use nodyn::nodyn;
+
+nodyn! {
+    #[derive(Debug, Clone)]
+    pub enum Key {
+        i32,
+        String,
+    }
+    impl transparent(Hash, Eq, Ord);
+}
+
+nodyn! {
+    #[derive(Debug, Clone)]
+    pub enum Name {
+        String,
+        Box<str>,
+    }
+    impl transparent(Hash, Eq, Ord, Borrow<str>);
+}
+
+nodyn! {
+    #[derive(Debug, Clone)]
+    pub enum Items<T> {
+        i32,
+        Vec<T>,
+    }
+    impl transparent(Hash, Eq, Ord);
+}
+
+fn main() {
+    let state = RandomState::new();
+    assert_eq!(state.hash_one(Key::I32(1)), state.hash_one(1i32));
+    assert_eq!(
+        state.hash_one(Key::from("one".to_string())),
+        state.hash_one("one".to_string())
+    );
+
+    assert_eq!(Key::I32(1), Key::I32(1));
+    assert_ne!(Key::I32(1), Key::I32(2));
+    assert_ne!(Key::I32(1), Key::from("1".to_string()));
+    assert_eq!(Key::I32(1), 1);
+
+    // different variants are ordered by declaration
+    assert!(Key::I32(2) < Key::I32(3));
+    assert!(Key::I32(100) < Key::from("a".to_string()));
+    let sorted = BTreeSet::from([Key::from("b".to_string()), Key::I32(7), Key::I32(-1)]);
+    assert_eq!(
+        sorted.into_iter().collect::<Vec<_>>(),
+        vec![Key::I32(-1), Key::I32(7), Key::from("b".to_string())]
+    );
+
+    let set = HashSet::from([Key::I32(1), Key::from("x".to_string())]);
+    assert!(set.contains(&Key::I32(1)));
+    assert!(!set.contains(&Key::I32(2)));
+
+    // with `Borrow<str>` variants compare through the borrowed `str`
+    assert_eq!(Name::from("a".to_string()), Name::from(Box::<str>::from("a")));
+    assert!(Name::from(Box::<str>::from("a")) < Name::from("b".to_string()));
+    assert_eq!(
+        state.hash_one(Name::from(Box::<str>::from("a"))),
+        state.hash_one("a")
+    );
+
+    let mut map = HashMap::new();
+    map.insert(Name::from("one".to_string()), 1);
+    map.insert(Name::from(Box::<str>::from("two")), 2);
+    assert_eq!(map.get("one"), Some(&1));
+    assert_eq!(map.get("two"), Some(&2));
+    assert_eq!(map.get("three"), None);
+
+    // generic variants are bounded by the traits
+    assert_eq!(Items::<u8>::from(vec![1]), Items::from(vec![1]));
+    assert!(Items::<u8>::I32(5) < Items::from(vec![0]));
+    assert_eq!(
+        state.hash_one(Items::<u8>::from(vec![1, 2])),
+        state.hash_one(vec![1u8, 2])
+    );
+}
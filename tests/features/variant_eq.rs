@@ -0,0 +1,53 @@
+use nodyn::nodyn;
+
+nodyn! {
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum Value {
+        i32,
+        String,
+        f64,
+    }
+    vec;
+}
+
+nodyn! {
+    #[derive(Debug, Clone)]
+    pub enum Number {
+        #[into(f64)]
+        i32,
+        f64,
+    }
+    impl compare;
+}
+
+nodyn! {
+    #[derive(Debug, PartialEq)]
+    pub enum Generic<'a, T> {
+        Vec<T>,
+        &'a str,
+    }
+}
+
+fn main() {
+    let value = Value::from(42);
+    assert_eq!(value, 42);
+    assert_eq!(42, value);
+    assert_ne!(value, 42.0);
+    assert_ne!(value, "42".to_string());
+    assert_eq!("hi".to_string(), Value::String("hi".to_string()));
+
+    let number = Number::from(42);
+    assert_eq!(number, 42.0);
+    assert_eq!(42.0, number);
+
+    let generic: Generic<u8> = "hello".into();
+    assert_eq!(generic, "hello");
+    let generic: Generic<u8> = vec![1u8].into();
+    assert_eq!(vec![1u8], generic);
+
+    let values = value_vec![1, "two".to_string(), 3.0];
+    assert!(values.contains_i32(&1));
+    assert!(!values.contains_i32(&2));
+    assert!(values.contains_string(&"two".to_string()));
+    assert!(values.contains_f64(&3.0));
+}
@@ -18,10 +18,35 @@ fn tests() {
     t.pass("tests/enum/custom_variants.rs");
     t.pass("tests/enum/from.rs");
     t.pass("tests/enum/generics.rs");
+    t.pass("tests/enum/delegation.rs");
 
     t.pass("tests/features/is_as.rs");
     t.pass("tests/features/introspection.rs");
     t.pass("tests/features/into_attribute.rs");
+    t.pass("tests/features/compare.rs");
+    t.pass("tests/features/variant_eq.rs");
+    t.pass("tests/features/from_str.rs");
+    t.pass("tests/features/parse_as.rs");
+    t.pass("tests/features/transparent_fmt.rs");
+    t.pass("tests/features/transparent_hash.rs");
+    t.pass("tests/features/iterator.rs");
+    t.pass("tests/features/returns.rs");
+    t.pass("tests/features/future.rs");
+    t.pass("tests/features/error.rs");
+    t.pass("tests/features/as_dyn.rs");
+    t.pass("tests/features/any.rs");
+    t.pass("tests/features/member.rs");
+    t.pass("tests/features/for_each_type.rs");
+    t.pass("tests/features/match_type.rs");
+    t.pass("tests/features/dispatch.rs");
+    t.pass("tests/features/combinators.rs");
+    t.pass("tests/features/tag.rs");
+    t.pass("tests/features/serde.rs");
+    t.pass("tests/features/codec.rs");
+    t.pass("tests/features/oneof.rs");
+    t.pass("tests/features/ffi.rs");
+    t.pass("tests/features/meta.rs");
+    t.pass("tests/features/naming.rs");
 
     t.pass("tests/vec_wrapper/standard.rs");
     t.pass("tests/vec_wrapper/custom.rs");
@@ -29,4 +54,18 @@ fn tests() {
 
     t.compile_fail("tests/errors/invalid_impl.rs");
     t.compile_fail("tests/errors/double_type.rs");
+    t.compile_fail("tests/errors/as_dyn_not_object_safe.rs");
+    t.compile_fail("tests/errors/member_sealed.rs");
+    t.compile_fail("tests/errors/match_type_unknown.rs");
+    t.compile_fail("tests/errors/tag_errors.rs");
+    t.compile_fail("tests/errors/ffi_not_copy.rs");
+    t.compile_fail("tests/errors/ffi_not_ffi_safe.rs");
+    t.compile_fail("tests/errors/attr_errors.rs");
+    t.compile_fail("tests/errors/unsupported_receiver.rs");
+    t.compile_fail("tests/errors/serde_attrs.rs");
+    t.compile_fail("tests/errors/meta_errors.rs");
+    t.compile_fail("tests/errors/naming_errors.rs");
+    t.compile_fail("tests/errors/transparent_eq_float.rs");
+    t.compile_fail("tests/errors/impl_conflicts.rs");
+    t.compile_fail("tests/errors/as_dyn_undeclared_method.rs");
 }